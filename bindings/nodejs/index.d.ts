@@ -37,6 +37,42 @@ export interface GolfShot {
   /** Spin axis angle in degrees (0 = pure backspin, positive = hook spin) */
   spin_axis_degrees?: number;
 
+  /** Static loft of the club in degrees (refines dynamic loft estimate) */
+  club_loft_degrees?: number;
+
+  /** Golfer handedness; left-handed shots get golfer-relative names and club path/face */
+  handedness?: 'right' | 'left';
+
+  /** Report lateral outputs mirrored (golfer-relative) for left-handed shots */
+  mirror_outputs?: boolean;
+
+  /** Target line in degrees (positive = right); direction and offline are judged against it */
+  target_bearing_degrees?: number;
+
+  /** Club label (e.g. "7i"), echoed in the output and used to group session statistics */
+  club?: string;
+
+  /** Classification preset: "beginner", "intermediate" (default) or "tour" */
+  skill_level?: 'beginner' | 'intermediate' | 'tour';
+
+  /** Custom classification thresholds; overrides skill_level */
+  classifier_config?: Record<string, number>;
+
+  /** "rules" (default) or "nearest_neighbor" to match against the reference library */
+  shot_classifier?: 'rules' | 'nearest_neighbor';
+
+  /** Rank matrix preset: "beginner" (default), "fade_bias", "draw_bias" or a registered name */
+  rank_scheme?: string;
+
+  /** Rank palette: "classic" (default), "colorblind", "high_contrast" or a registered name */
+  color_theme?: string;
+
+  /** Locale tag (e.g. "es-MX") for localized shot text in the output */
+  locale?: string;
+
+  /** Previous shot's label; near-boundary shots keep it instead of flickering */
+  previous_shot_name?: string;
+
   /** Calculated carry distance in meters */
   carry_distance_meters?: number;
 
@@ -70,6 +106,21 @@ export interface GolfShot {
   /** Estimated face-to-path relationship */
   club_face_to_path_degrees?: number;
 
+  /** Estimated angle of attack (positive = hitting up) */
+  attack_angle_degrees?: number;
+
+  /** Estimated loft delivered at impact */
+  dynamic_loft_degrees?: number;
+
+  /** Dynamic loft minus attack angle */
+  spin_loft_degrees?: number;
+
+  /** Detected strike quality */
+  contact_quality?: 'solid' | 'thin' | 'fat' | 'topped' | 'skied' | 'toe' | 'heel';
+
+  /** Stable shot ID matching locales/shots keys, e.g. "push_draw" */
+  shot_id?: string;
+
   /** Classified shot label */
   shot_name?: string;
 
@@ -79,6 +130,69 @@ export interface GolfShot {
   /** Recommended display color for the shot */
   shot_color_rgb?: string;
 
+  /** Nearest-neighbor match (with shot_classifier "nearest_neighbor") */
+  reference_match?: {
+    label: string;
+    name?: string;
+    rank?: string;
+    confidence: number;
+    distance: number;
+    neighbors: Array<{ label: string; distance: number }>;
+  };
+
+  /** Shot name in the requested locale */
+  localized_shot_name?: string;
+
+  /** Localized shot description (requires locale) */
+  shot_description?: string;
+
+  /** Localized coaching tip (requires locale) */
+  shot_coaching_tip?: string;
+
+  /** Coaching recommendations, priority 1 first; empty when nothing needs work */
+  recommendations?: Array<{
+    id: string;
+    priority: number;
+    metric: string;
+    value: number;
+    target_min?: number;
+    target_max?: number;
+    title: string;
+    advice: string;
+  }>;
+
+  /** 0.5 on a classification boundary, 1.0 when clear of every boundary */
+  shot_confidence?: number;
+
+  /** Runner-up labels, closest first */
+  shot_alternatives?: Array<{
+    shot_id: string;
+    shot_name: string;
+    shot_rank: string;
+    boundary_distance_degrees: number;
+  }>;
+
+  /** Target distance in meters; enables outcome classification */
+  target_distance_meters?: number;
+
+  /** Half-width of the "on target" window in meters (default 5% of target distance, min 3 m) */
+  target_tolerance_meters?: number;
+
+  /** Share of the outcome rank in combined_rank (0-1, default 0.5) */
+  outcome_weight?: number;
+
+  /** Where the ball finished relative to the target ("On target", "Long left", ...) */
+  outcome_name?: string;
+
+  /** Rank for how close the ball finished to the target */
+  outcome_rank?: string;
+
+  /** Blend of shot_rank and outcome_rank */
+  combined_rank?: string;
+
+  /** Distance from the finishing position to the target in meters */
+  distance_to_target_meters?: number;
+
   /** Carry distance in yards (converted to meters) */
   carry_distance_yards?: number;
 
@@ -108,3 +222,20 @@ export interface GolfShot {
  * @returns Shot data with added derived values
  */
 export function calculateDerivedValues(shotData: GolfShot): GolfShot;
+
+/** A shot in a batch that could not be processed */
+export interface BatchItemError {
+  error: {
+    /** Position of the shot in the batch, from 0 */
+    index: number;
+    message: string;
+  };
+}
+
+/**
+ * Calculate derived values for many shots in one call
+ *
+ * @param shots - Golf shot parameters
+ * @returns Results in input order, with an error in place of each shot that failed
+ */
+export function calculateDerivedValuesBatch(shots: GolfShot[]): Array<GolfShot | BatchItemError>;
@@ -38,6 +38,19 @@ function calculateDerivedValues(shotData) {
   return JSON.parse(jsonOutput);
 }
 
+/**
+ * Calculate derived values for many shots in one call
+ *
+ * @param {Object[]} shots - Golf shot parameters, as for calculateDerivedValues
+ * @returns {Object[]} Results in the same order; a shot that failed is replaced by
+ *   { error: { index, message } }
+ */
+function calculateDerivedValuesBatch(shots) {
+  const jsonOutput = wasm.calculate_derived_values_batch(JSON.stringify(shots));
+  return JSON.parse(jsonOutput);
+}
+
 module.exports = {
-  calculateDerivedValues
+  calculateDerivedValues,
+  calculateDerivedValuesBatch
 };
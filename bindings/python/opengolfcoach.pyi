@@ -25,9 +25,54 @@ class USCustomaryUnits(TypedDict, total=False):
     optimal_maximum_distance_yards: float
 
 
+class ShotAlternative(TypedDict):
+    """Neighbouring shot label and how far the shot is from it."""
+
+    shot_id: str
+    shot_name: str
+    shot_rank: str
+    boundary_distance_degrees: float
+
+
+class ReferenceNeighbor(TypedDict):
+    """Reference shot that voted in a nearest-neighbor match."""
+
+    label: str
+    distance: float
+
+
+class ReferenceMatch(TypedDict, total=False):
+    """Nearest-neighbor classification result."""
+
+    label: str
+    name: str
+    rank: str
+    confidence: float
+    distance: float
+    neighbors: list[ReferenceNeighbor]
+
+
+class Recommendation(TypedDict, total=False):
+    """Coaching recommendation and the metric that triggered it."""
+
+    id: str
+    priority: int
+    metric: str
+    value: float
+    target_min: float
+    target_max: float
+    title: str
+    advice: str
+
+
 class DerivedValues(TypedDict, total=False):
     """Derived values calculated by OpenGolfCoach."""
 
+    club: str
+    ball_speed_meters_per_second: float
+    vertical_launch_angle_degrees: float
+    club_loft_degrees: float
+    target_distance_meters: float
     backspin_rpm: float
     sidespin_rpm: float
     total_spin_rpm: float
@@ -47,9 +92,25 @@ class DerivedValues(TypedDict, total=False):
     club_path_degrees: float
     club_face_to_target_degrees: float
     club_face_to_path_degrees: float
+    attack_angle_degrees: float
+    dynamic_loft_degrees: float
+    spin_loft_degrees: float
+    contact_quality: str
+    shot_id: str
     shot_name: str
     shot_rank: str
     shot_color_rgb: str
+    shot_confidence: float
+    shot_alternatives: list[ShotAlternative]
+    reference_match: ReferenceMatch
+    localized_shot_name: str
+    shot_description: str
+    shot_coaching_tip: str
+    recommendations: list[Recommendation]
+    outcome_name: str
+    outcome_rank: str
+    combined_rank: str
+    distance_to_target_meters: float
     us_customary_units: USCustomaryUnits
     pressure_pascals: float
     elevation_meters: float
@@ -99,5 +160,335 @@ def calculate_derived_values(json_input: str) -> str:
     ...
 
 
+def calculate_derived_values_batch(json_input: str, threads: int | None = None) -> str:
+    """
+    Calculate derived values for many shots at once, in parallel.
+
+    Args:
+        json_input: JSON array of shots, or JSON Lines (one shot per line; blank lines are
+            skipped).
+        threads: Worker threads; defaults to the number of CPUs.
+
+    Returns:
+        Results in the input's layout and order, each shot with ``open_golf_coach`` added.
+        A shot that failed is replaced by ``{"error": {"index": ..., "line": ...,
+        "message": ...}}`` (``line`` only for JSON Lines).
+
+    Raises:
+        ValueError: If a JSON array is malformed.
+    """
+    ...
+
+
+def load_impact_bands(toml_text: str) -> None:
+    """
+    Replace the impact bands used by clubhead estimates.
+
+    Args:
+        toml_text: TOML document with ``[[bands]]`` tables ordered by ball speed,
+            in the same layout as ``shot_classification/impact_bands.toml``.
+
+    Raises:
+        ValueError: If the TOML is malformed or the bands fail validation.
+    """
+    ...
+
+
+def reset_impact_bands() -> None:
+    """Restore the impact bands shipped with the library."""
+    ...
+
+
+def calibrate_ball_model(shots_json: str) -> str:
+    """
+    Fit the aerodynamic ball model to launch-monitor shots with measured carry.
+
+    Args:
+        shots_json: JSON array of shots with ``ball_speed_meters_per_second``,
+            ``vertical_launch_angle_degrees``, ``total_spin_rpm`` and
+            ``measured_carry_distance_meters`` (``measured_peak_height_meters`` optional).
+
+    Returns:
+        JSON object with the fitted ``model`` and carry/peak-height residual statistics.
+
+    Raises:
+        ValueError: If the JSON is malformed or no shot is usable.
+    """
+    ...
+
+
+def summarize_session(shots_json: str) -> str:
+    """
+    Per-club statistics for a session of shots.
+
+    Args:
+        shots_json: JSON array of ``calculate_derived_values`` results (or bare
+            ``open_golf_coach`` objects), tagged with ``club`` to group them.
+
+    Returns:
+        JSON object with mean, median, standard deviation, min/max and percentiles of
+        carry, total, offline, ball speed, club speed, smash factor, spin and launch per club
+        and ``overall``.
+
+    Raises:
+        ValueError: If the JSON is malformed.
+    """
+    ...
+
+
+def analyze_dispersion(shots_json: str, options_json: str | None = None) -> str:
+    """
+    Landing dispersion of a group of shots.
+
+    Args:
+        shots_json: JSON array of ``calculate_derived_values`` results (or bare
+            ``open_golf_coach`` objects) with ``landing_position``.
+        options_json: Optional JSON object with ``confidence_levels`` (default
+            ``[0.68, 0.95]``), ``target_distance_meters``, ``target_bearing_degrees``,
+            ``bias_tolerance_meters`` and ``outlier_confidence`` (default 0.99).
+
+    Returns:
+        JSON object with the centroid, covariance ellipses, left/right and short/long bias
+        and outliers, or ``null`` when no shot has a landing position.
+
+    Raises:
+        ValueError: If the JSON is malformed.
+    """
+    ...
+
+
+def analyze_trends(shots_json: str, options_json: str | None = None) -> str:
+    """
+    Significant trends (fatigue or improvement) within each club's shots, found by sliding
+    a window over them.
+
+    Args:
+        shots_json: JSON array of ``calculate_derived_values`` results (or bare
+            ``open_golf_coach`` objects) in the order they were hit.
+        options_json: Optional JSON object with ``metrics`` (default club speed, ball speed,
+            smash factor and carry), ``window_shots`` (20), ``min_shots`` (8),
+            ``significance`` (0.05) and ``min_change_percent`` (2).
+
+    Returns:
+        JSON array of findings, largest change first, each with the metric, club, direction,
+        first/last shot index, fitted start/end values, change percent, corrected p-value
+        and a message such as ``"Driver: club speed dropped 4.1% over the last 20 shots"``
+        or ``"... over Driver shots 11-30"`` (counting that club's shots) for a change
+        earlier in the session.
+
+    Raises:
+        ValueError: If the JSON is malformed.
+    """
+    ...
+
+
+def gapping_report(shots_json: str, options_json: str | None = None, csv: bool = False) -> str:
+    """
+    Club gapping report for a session of club-tagged shots.
+
+    Args:
+        shots_json: JSON array of ``calculate_derived_values`` results (or bare
+            ``open_golf_coach`` objects) with ``club`` and, for suggested lofts,
+            ``club_loft_degrees``.
+        options_json: Optional JSON object with ``target_gap_meters`` (default 11),
+            ``min_gap_meters`` (6), ``max_gap_meters`` (18), ``carry_per_loft_degree_meters``
+            (2.5), ``max_loft_change_degrees`` (2) and ``min_shots`` (3).
+        csv: Return one CSV row per club instead of JSON.
+
+    Returns:
+        Stock carry/total per club (longest first), gaps with ``good``/``overlap``/``hole``
+        status and suggested loft changes, as JSON or CSV.
+
+    Raises:
+        ValueError: If the JSON is malformed.
+    """
+    ...
+
+
+def compare_to_benchmark(
+    shots_json: str, band: str | None = None, club: str | None = None
+) -> str:
+    """
+    Compare shots with one club against a handicap band.
+
+    Args:
+        shots_json: JSON array of ``calculate_derived_values`` results (or bare
+            ``open_golf_coach`` objects). Strokes gained needs ``target_distance_meters`` on
+            the input.
+        band: ``tour``, ``scratch``, ``mid_handicap`` or ``high_handicap``; defaults to
+            ``mid_handicap``.
+        club: Club to compare (``"7 Iron"``, ``"7i"``); defaults to the shots' own club.
+
+    Returns:
+        JSON comparison with a percentile for ball speed, launch, spin and carry, an
+        accuracy percentile and average strokes gained per shot.
+
+    Raises:
+        ValueError: If the JSON is malformed, the band or club is unknown, or the shots use
+            several clubs and ``club`` is not given.
+    """
+    ...
+
+
+def load_benchmarks(toml_text: str) -> None:
+    """
+    Replace the benchmark tables used by ``compare_to_benchmark``.
+
+    Args:
+        toml_text: TOML document laid out like ``shot_classification/benchmarks.toml``.
+
+    Raises:
+        ValueError: If the TOML is malformed or the tables fail validation.
+    """
+    ...
+
+
+def reset_benchmarks() -> None:
+    """Restore the benchmark tables shipped with the library."""
+    ...
+
+
+def load_ball_model(toml_text: str) -> None:
+    """
+    Replace the ball model used by trajectory calculations.
+
+    Args:
+        toml_text: TOML document with ``drag_scale``, ``lift_scale`` and optional
+            ``spin_decay_rate``.
+
+    Raises:
+        ValueError: If the TOML is malformed or the model fails validation.
+    """
+    ...
+
+
+def reset_ball_model() -> None:
+    """Restore the uncalibrated ball model."""
+    ...
+
+
+def load_classifier_config(toml_text: str) -> None:
+    """
+    Replace the default shot classification thresholds.
+
+    Args:
+        toml_text: TOML document with the threshold keys used by the presets in
+            ``shot_classification/classifier_config.toml``. Missing keys keep the
+            intermediate defaults.
+
+    Raises:
+        ValueError: If the TOML is malformed or the thresholds fail validation.
+    """
+    ...
+
+
+def reset_classifier_config() -> None:
+    """Restore the default shot classification thresholds."""
+    ...
+
+
+def load_rank_schemes(toml_text: str) -> list[str]:
+    """
+    Register rank schemes from TOML laid out like ``shot_classification/rank_schemes.toml``.
+
+    Returns:
+        The scheme names found.
+
+    Raises:
+        ValueError: If the TOML is malformed or a scheme uses an unknown rank.
+    """
+    ...
+
+
+def reset_rank_schemes() -> None:
+    """Restore the rank schemes shipped with the library."""
+    ...
+
+
+def load_color_themes(toml_text: str) -> list[str]:
+    """
+    Register color themes from TOML laid out like ``shot_classification/color_themes.toml``.
+
+    Returns:
+        The theme names found.
+
+    Raises:
+        ValueError: If the TOML is malformed or a theme misses a rank or has a bad color.
+    """
+    ...
+
+
+def reset_color_themes() -> None:
+    """Restore the color themes shipped with the library."""
+    ...
+
+
+def load_reference_library(toml_text: str) -> None:
+    """
+    Replace the reference shots used by ``"shot_classifier": "nearest_neighbor"``.
+
+    Args:
+        toml_text: TOML document laid out like ``shot_classification/reference_shots.toml``.
+
+    Raises:
+        ValueError: If the TOML is malformed or the library fails validation.
+    """
+    ...
+
+
+def reset_reference_library() -> None:
+    """Restore the reference shots shipped with the library."""
+    ...
+
+
+class ShotDescription(TypedDict):
+    """Localized text for a shot."""
+
+    shot_id: str
+    name: str
+    description: str
+    coaching_tip: str
+
+
+def describe_shot(shot_id: str, locale: str = "en") -> str:
+    """
+    Localized name, description and coaching tip for a shot.
+
+    Args:
+        shot_id: Shot ID such as ``"push_draw"`` (the English name is also accepted).
+        locale: Locale tag; missing text falls back to the language and then English.
+
+    Returns:
+        JSON object matching ``ShotDescription``.
+
+    Raises:
+        ValueError: If the shot ID is unknown.
+    """
+    ...
+
+
+def define_field(field: str, locale: str = "en") -> str | None:
+    """Plain-language definition of an input or output field, or ``None`` if unknown."""
+    ...
+
+
+def load_locale_directory(path: str) -> list[str]:
+    """
+    Load every locale file in a directory laid out like ``locales/``.
+
+    Returns:
+        The locale tags found.
+
+    Raises:
+        ValueError: If a file cannot be read or parsed.
+    """
+    ...
+
+
+def reset_locales() -> None:
+    """Drop runtime-loaded locales and restore the embedded English text."""
+    ...
+
+
 __all__: list[str]
 __version__: str
@@ -19,7 +19,62 @@ Example:
     >>> print(result["open_golf_coach"]["carry_distance_meters"])
 """
 
-from .opengolfcoach import calculate_derived_values
+from .opengolfcoach import (
+    analyze_dispersion,
+    analyze_trends,
+    calculate_derived_values,
+    calculate_derived_values_batch,
+    calibrate_ball_model,
+    compare_to_benchmark,
+    define_field,
+    describe_shot,
+    gapping_report,
+    load_ball_model,
+    load_benchmarks,
+    load_classifier_config,
+    load_color_themes,
+    load_impact_bands,
+    load_locale_directory,
+    load_rank_schemes,
+    load_reference_library,
+    reset_ball_model,
+    reset_benchmarks,
+    reset_classifier_config,
+    reset_color_themes,
+    reset_impact_bands,
+    reset_locales,
+    reset_rank_schemes,
+    reset_reference_library,
+    summarize_session,
+)
 
 __version__ = "0.1.0"
-__all__ = ["calculate_derived_values", "__version__"]
+__all__ = [
+    "analyze_dispersion",
+    "analyze_trends",
+    "calculate_derived_values",
+    "calculate_derived_values_batch",
+    "calibrate_ball_model",
+    "compare_to_benchmark",
+    "define_field",
+    "describe_shot",
+    "gapping_report",
+    "load_ball_model",
+    "load_benchmarks",
+    "load_classifier_config",
+    "load_color_themes",
+    "load_impact_bands",
+    "load_locale_directory",
+    "load_rank_schemes",
+    "load_reference_library",
+    "reset_ball_model",
+    "reset_benchmarks",
+    "reset_classifier_config",
+    "reset_color_themes",
+    "reset_impact_bands",
+    "reset_locales",
+    "reset_rank_schemes",
+    "reset_reference_library",
+    "summarize_session",
+    "__version__",
+]
@@ -34,6 +34,407 @@ fn calculate_derived_values(json_input: &str) -> PyResult<String> {
     }
 }
 
+/// Calculate derived values for many shots at once, in parallel
+///
+/// Args:
+///     json_input (str): JSON array of shots, or JSON Lines (one shot per line)
+///     threads (int, optional): Worker threads; defaults to the number of CPUs
+///
+/// Returns:
+///     str: Results in the input's layout and order; a shot that failed is replaced by
+///         {"error": {"index": ..., "line": ..., "message": ...}}
+///
+/// Raises:
+///     ValueError: If a JSON array is malformed
+#[pyfunction]
+#[pyo3(signature = (json_input, threads=None))]
+fn calculate_derived_values_batch(
+    py: Python<'_>,
+    json_input: &str,
+    threads: Option<usize>,
+) -> PyResult<String> {
+    let options = ::opengolfcoach::BatchOptions { threads };
+    py.allow_threads(|| ::opengolfcoach::calculate_batch_json(json_input, &options))
+        .map(|results| results.to_json_string())
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+}
+
+/// Replace the impact bands used by clubhead estimates with bands from TOML text
+///
+/// The text uses the same `[[bands]]` layout as `shot_classification/impact_bands.toml`.
+/// The new bands apply to every subsequent calculation in this process.
+///
+/// Args:
+///     toml_text (str): TOML document with `[[bands]]` tables ordered by ball speed
+///
+/// Raises:
+///     ValueError: If the TOML is malformed or the bands fail validation
+#[pyfunction]
+fn load_impact_bands(toml_text: &str) -> PyResult<()> {
+    ::opengolfcoach::load_impact_bands_from_str(toml_text)
+        .and_then(::opengolfcoach::set_impact_bands)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+}
+
+/// Restore the impact bands shipped with the library
+#[pyfunction]
+fn reset_impact_bands() {
+    ::opengolfcoach::reset_impact_bands();
+}
+
+/// Fit the aerodynamic ball model to launch-monitor shots with measured carry
+///
+/// Each shot needs ball speed, vertical launch angle, total spin and
+/// `measured_carry_distance_meters`; `measured_peak_height_meters` is used when present.
+///
+/// Args:
+///     shots_json (str): JSON array of calibration shots
+///
+/// Returns:
+///     str: JSON object with the fitted `model` and residual statistics
+///
+/// Raises:
+///     ValueError: If the JSON is malformed or no shot is usable
+#[pyfunction]
+fn calibrate_ball_model(shots_json: &str) -> PyResult<String> {
+    let to_py_err = |msg: String| PyErr::new::<pyo3::exceptions::PyValueError, _>(msg);
+    let shots: Vec<::opengolfcoach::CalibrationShot> =
+        serde_json::from_str(shots_json).map_err(|e| to_py_err(e.to_string()))?;
+    let result = ::opengolfcoach::calibrate_ball_model(&shots, &Default::default())
+        .map_err(|e| to_py_err(e.to_string()))?;
+    serde_json::to_string(&result).map_err(|e| to_py_err(e.to_string()))
+}
+
+/// Per-club statistics for a session of shots
+///
+/// Args:
+///     shots_json (str): JSON array of `calculate_derived_values` results (or bare
+///         "open_golf_coach" objects), tagged with `club` to group them
+///
+/// Returns:
+///     str: JSON object with per-club and `overall` statistics
+///
+/// Raises:
+///     ValueError: If the JSON is malformed
+#[pyfunction]
+fn summarize_session(shots_json: &str) -> PyResult<String> {
+    let to_py_err = |msg: String| PyErr::new::<pyo3::exceptions::PyValueError, _>(msg);
+    let session = session_from_json(shots_json).map_err(|e| to_py_err(e.to_string()))?;
+    serde_json::to_string(&session.summary()).map_err(|e| to_py_err(e.to_string()))
+}
+
+/// Landing dispersion of a group of shots
+///
+/// Args:
+///     shots_json (str): JSON array of `calculate_derived_values` results (or bare
+///         "open_golf_coach" objects) with `landing_position`
+///     options_json (str, optional): JSON object with `confidence_levels`,
+///         `target_distance_meters`, `target_bearing_degrees`, `bias_tolerance_meters`
+///         and `outlier_confidence`
+///
+/// Returns:
+///     str: JSON object with centroid, ellipses, bias and outliers, or "null" when no
+///         shot has a landing position
+///
+/// Raises:
+///     ValueError: If the JSON is malformed
+#[pyfunction]
+#[pyo3(signature = (shots_json, options_json=None))]
+fn analyze_dispersion(shots_json: &str, options_json: Option<&str>) -> PyResult<String> {
+    let to_py_err = |msg: String| PyErr::new::<pyo3::exceptions::PyValueError, _>(msg);
+    let session = session_from_json(shots_json).map_err(|e| to_py_err(e.to_string()))?;
+    let options: ::opengolfcoach::DispersionOptions = match options_json {
+        Some(text) => serde_json::from_str(text).map_err(|e| to_py_err(e.to_string()))?,
+        None => Default::default(),
+    };
+    let pattern = session.dispersion(::opengolfcoach::ClubSelector::All, &options);
+    serde_json::to_string(&pattern).map_err(|e| to_py_err(e.to_string()))
+}
+
+/// Significant trends (fatigue or improvement) within each club's shots, found by sliding a window
+/// over them
+///
+/// Args:
+///     shots_json (str): JSON array of `calculate_derived_values` results (or bare
+///         "open_golf_coach" objects) in the order they were hit
+///     options_json (str, optional): JSON object with `metrics`, `window_shots`, `min_shots`,
+///         `significance` and `min_change_percent`
+///
+/// Returns:
+///     str: JSON array of findings, largest change first
+///
+/// Raises:
+///     ValueError: If the JSON is malformed
+#[pyfunction]
+#[pyo3(signature = (shots_json, options_json=None))]
+fn analyze_trends(shots_json: &str, options_json: Option<&str>) -> PyResult<String> {
+    let to_py_err = |msg: String| PyErr::new::<pyo3::exceptions::PyValueError, _>(msg);
+    let session = session_from_json(shots_json).map_err(|e| to_py_err(e.to_string()))?;
+    let options: ::opengolfcoach::TrendOptions = match options_json {
+        Some(text) => serde_json::from_str(text).map_err(|e| to_py_err(e.to_string()))?,
+        None => Default::default(),
+    };
+    serde_json::to_string(&session.trends(&options)).map_err(|e| to_py_err(e.to_string()))
+}
+
+/// Club gapping report for a session of club-tagged shots
+///
+/// Args:
+///     shots_json (str): JSON array of `calculate_derived_values` results (or bare
+///         "open_golf_coach" objects) with `club` and, for loft suggestions, `club_loft_degrees`
+///     options_json (str, optional): JSON object with `target_gap_meters`, `min_gap_meters`,
+///         `max_gap_meters`, `carry_per_loft_degree_meters`, `max_loft_change_degrees` and `min_shots`
+///     csv (bool): Return one CSV row per club instead of JSON
+///
+/// Returns:
+///     str: JSON report with clubs, gaps and skipped clubs, or CSV text
+///
+/// Raises:
+///     ValueError: If the JSON is malformed
+#[pyfunction]
+#[pyo3(signature = (shots_json, options_json=None, csv=false))]
+fn gapping_report(shots_json: &str, options_json: Option<&str>, csv: bool) -> PyResult<String> {
+    let to_py_err = |msg: String| PyErr::new::<pyo3::exceptions::PyValueError, _>(msg);
+    let session = session_from_json(shots_json).map_err(|e| to_py_err(e.to_string()))?;
+    let options: ::opengolfcoach::GappingOptions = match options_json {
+        Some(text) => serde_json::from_str(text).map_err(|e| to_py_err(e.to_string()))?,
+        None => Default::default(),
+    };
+    let report = session.gapping_report(&options);
+    if csv {
+        Ok(report.to_csv())
+    } else {
+        serde_json::to_string(&report).map_err(|e| to_py_err(e.to_string()))
+    }
+}
+
+/// Compare shots with one club against a handicap band of the benchmark tables
+///
+/// Args:
+///     shots_json (str): JSON array of `calculate_derived_values` results (or bare
+///         "open_golf_coach" objects); shots need `target_distance_meters` for strokes gained
+///     band (str, optional): "tour", "scratch", "mid_handicap" or "high_handicap"; defaults to
+///         the tables' default band
+///     club (str, optional): Club to compare, e.g. "7 Iron"; defaults to the shots' own club
+///
+/// Returns:
+///     str: JSON comparison with per-metric percentiles, accuracy percentile and strokes gained
+///
+/// Raises:
+///     ValueError: If the JSON is malformed, the band or club is unknown, or the club is ambiguous
+#[pyfunction]
+#[pyo3(signature = (shots_json, band=None, club=None))]
+fn compare_to_benchmark(
+    shots_json: &str,
+    band: Option<&str>,
+    club: Option<&str>,
+) -> PyResult<String> {
+    let to_py_err = |msg: String| PyErr::new::<pyo3::exceptions::PyValueError, _>(msg);
+    let session = session_from_json(shots_json).map_err(|e| to_py_err(e.to_string()))?;
+    let comparison = ::opengolfcoach::compare_to_benchmark(session.shots(), band, club)
+        .map_err(|e| to_py_err(e.to_string()))?;
+    serde_json::to_string(&comparison).map_err(|e| to_py_err(e.to_string()))
+}
+
+/// Collect shot results, taking the "open_golf_coach" object when present
+fn session_from_json(shots_json: &str) -> serde_json::Result<::opengolfcoach::Session> {
+    let shots: Vec<serde_json::Value> = serde_json::from_str(shots_json)?;
+    shots
+        .into_iter()
+        .map(|mut shot| {
+            let derived = match shot.get_mut("open_golf_coach") {
+                Some(derived) => derived.take(),
+                None => shot,
+            };
+            serde_json::from_value(derived)
+        })
+        .collect()
+}
+
+/// Replace the ball model used by trajectory calculations with a model from TOML text
+///
+/// Args:
+///     toml_text (str): TOML document with `drag_scale`, `lift_scale` and optional `spin_decay_rate`
+///
+/// Raises:
+///     ValueError: If the TOML is malformed or the model fails validation
+#[pyfunction]
+fn load_ball_model(toml_text: &str) -> PyResult<()> {
+    ::opengolfcoach::load_ball_model_from_str(toml_text)
+        .and_then(::opengolfcoach::set_ball_model)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+}
+
+/// Restore the uncalibrated ball model
+#[pyfunction]
+fn reset_ball_model() {
+    ::opengolfcoach::reset_ball_model();
+}
+
+/// Replace the benchmark tables used by `compare_to_benchmark` with tables from TOML text
+///
+/// Args:
+///     toml_text (str): TOML document laid out like `shot_classification/benchmarks.toml`
+///
+/// Raises:
+///     ValueError: If the TOML is malformed or the tables fail validation
+#[pyfunction]
+fn load_benchmarks(toml_text: &str) -> PyResult<()> {
+    ::opengolfcoach::load_benchmarks_from_str(toml_text)
+        .and_then(::opengolfcoach::set_benchmarks)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+}
+
+/// Restore the benchmark tables shipped with the library
+#[pyfunction]
+fn reset_benchmarks() {
+    ::opengolfcoach::reset_benchmarks();
+}
+
+/// Replace the default shot classification thresholds with a config from TOML text
+///
+/// Keys match the presets in `shot_classification/classifier_config.toml`; missing keys
+/// keep the intermediate defaults. Shots that set `skill_level` or `classifier_config`
+/// still use their own thresholds.
+///
+/// Args:
+///     toml_text (str): TOML document with classifier threshold keys
+///
+/// Raises:
+///     ValueError: If the TOML is malformed or the thresholds fail validation
+#[pyfunction]
+fn load_classifier_config(toml_text: &str) -> PyResult<()> {
+    ::opengolfcoach::load_classifier_config_from_str(toml_text)
+        .and_then(::opengolfcoach::set_classifier_config)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+}
+
+/// Restore the default shot classification thresholds
+#[pyfunction]
+fn reset_classifier_config() {
+    ::opengolfcoach::reset_classifier_config();
+}
+
+/// Register rank schemes from TOML text
+///
+/// Uses the `[schemes.<name>]` layout of `shot_classification/rank_schemes.toml`; shots
+/// pick a scheme with `"rank_scheme": "<name>"`.
+///
+/// Args:
+///     toml_text (str): TOML document with one or more schemes
+///
+/// Returns:
+///     list[str]: The scheme names found
+///
+/// Raises:
+///     ValueError: If the TOML is malformed or a scheme uses an unknown rank
+#[pyfunction]
+fn load_rank_schemes(toml_text: &str) -> PyResult<Vec<String>> {
+    ::opengolfcoach::load_rank_schemes_from_str(toml_text)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+}
+
+/// Restore the rank schemes shipped with the library
+#[pyfunction]
+fn reset_rank_schemes() {
+    ::opengolfcoach::reset_rank_schemes();
+}
+
+/// Register color themes from TOML text
+///
+/// Uses the `[themes.<name>]` layout of `shot_classification/color_themes.toml`; shots
+/// pick a theme with `"color_theme": "<name>"`.
+///
+/// Args:
+///     toml_text (str): TOML document with one or more themes
+///
+/// Returns:
+///     list[str]: The theme names found
+///
+/// Raises:
+///     ValueError: If the TOML is malformed or a theme misses a rank or has a bad color
+#[pyfunction]
+fn load_color_themes(toml_text: &str) -> PyResult<Vec<String>> {
+    ::opengolfcoach::load_color_themes_from_str(toml_text)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+}
+
+/// Restore the color themes shipped with the library
+#[pyfunction]
+fn reset_color_themes() {
+    ::opengolfcoach::reset_color_themes();
+}
+
+/// Replace the reference shots used by the nearest-neighbor classifier
+///
+/// Args:
+///     toml_text (str): TOML document laid out like `shot_classification/reference_shots.toml`
+///
+/// Raises:
+///     ValueError: If the TOML is malformed or the library fails validation
+#[pyfunction]
+fn load_reference_library(toml_text: &str) -> PyResult<()> {
+    ::opengolfcoach::load_reference_library_from_str(toml_text)
+        .and_then(::opengolfcoach::set_reference_library)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+}
+
+/// Restore the reference shots shipped with the library
+#[pyfunction]
+fn reset_reference_library() {
+    ::opengolfcoach::reset_reference_library();
+}
+
+/// Localized name, description and coaching tip for a shot
+///
+/// Args:
+///     shot_id (str): Shot ID such as "push_draw" (the English name is also accepted)
+///     locale (str): Locale tag; missing text falls back to the language and then English
+///
+/// Returns:
+///     str: JSON object with `shot_id`, `name`, `description` and `coaching_tip`
+///
+/// Raises:
+///     ValueError: If the shot ID is unknown
+#[pyfunction]
+#[pyo3(signature = (shot_id, locale = "en"))]
+fn describe_shot(shot_id: &str, locale: &str) -> PyResult<String> {
+    let to_py_err = |msg: String| PyErr::new::<pyo3::exceptions::PyValueError, _>(msg);
+    let id = ::opengolfcoach::ShotId::from_label(shot_id)
+        .ok_or_else(|| to_py_err(format!("Unknown shot ID: {}", shot_id)))?;
+    serde_json::to_string(&::opengolfcoach::describe_shot(id, locale))
+        .map_err(|e| to_py_err(e.to_string()))
+}
+
+/// Plain-language definition of an input or output field, or None if unknown
+#[pyfunction]
+#[pyo3(signature = (field, locale = "en"))]
+fn define_field(field: &str, locale: &str) -> Option<String> {
+    ::opengolfcoach::define_field(field, locale)
+}
+
+/// Load every locale file in a directory laid out like `locales/`
+///
+/// Args:
+///     path (str): Directory with `shots/` and/or `definitions/` TOML files
+///
+/// Returns:
+///     list[str]: The locale tags found
+///
+/// Raises:
+///     ValueError: If a file cannot be read or parsed
+#[pyfunction]
+fn load_locale_directory(path: &str) -> PyResult<Vec<String>> {
+    ::opengolfcoach::load_locale_directory(path)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+}
+
+/// Drop runtime-loaded locales and restore the embedded English text
+#[pyfunction]
+fn reset_locales() {
+    ::opengolfcoach::reset_locales();
+}
+
 /// OpenGolfCoach - Calculate derived golf shot values
 ///
 /// This module provides functions to calculate derived golf metrics such as:
@@ -45,5 +446,30 @@ fn calculate_derived_values(json_input: &str) -> PyResult<String> {
 #[pymodule]
 fn opengolfcoach(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(calculate_derived_values, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_derived_values_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(load_impact_bands, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_impact_bands, m)?)?;
+    m.add_function(wrap_pyfunction!(calibrate_ball_model, m)?)?;
+    m.add_function(wrap_pyfunction!(summarize_session, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_dispersion, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_trends, m)?)?;
+    m.add_function(wrap_pyfunction!(gapping_report, m)?)?;
+    m.add_function(wrap_pyfunction!(compare_to_benchmark, m)?)?;
+    m.add_function(wrap_pyfunction!(load_benchmarks, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_benchmarks, m)?)?;
+    m.add_function(wrap_pyfunction!(load_ball_model, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_ball_model, m)?)?;
+    m.add_function(wrap_pyfunction!(load_classifier_config, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_classifier_config, m)?)?;
+    m.add_function(wrap_pyfunction!(load_rank_schemes, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_rank_schemes, m)?)?;
+    m.add_function(wrap_pyfunction!(load_color_themes, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_color_themes, m)?)?;
+    m.add_function(wrap_pyfunction!(load_reference_library, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_reference_library, m)?)?;
+    m.add_function(wrap_pyfunction!(describe_shot, m)?)?;
+    m.add_function(wrap_pyfunction!(define_field, m)?)?;
+    m.add_function(wrap_pyfunction!(load_locale_directory, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_locales, m)?)?;
     Ok(())
 }
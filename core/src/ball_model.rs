@@ -0,0 +1,146 @@
+use crate::config_file::{load_file, parse_toml, save_file, ConfigError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+static BALL_MODEL: OnceLock<RwLock<BallModel>> = OnceLock::new();
+
+pub(crate) const DATA: &str = "ball model";
+
+/// Default spin decay, fraction of spin lost per second
+const DEFAULT_SPIN_DECAY_RATE: f64 = 0.04;
+
+/// Scale factors applied to the aerodynamic model in `calculate_trajectory`
+///
+/// The default model (all scales 1.0) reproduces the uncalibrated drag and lift tables.
+/// Calibrated models are produced by [`crate::calibrate_ball_model`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BallModel {
+    /// Multiplier on the drag coefficient
+    pub drag_scale: f64,
+
+    /// Multiplier on the lift coefficient
+    pub lift_scale: f64,
+
+    /// Fraction of spin lost per second (exponential decay)
+    #[serde(default = "default_spin_decay_rate")]
+    pub spin_decay_rate: f64,
+}
+
+fn default_spin_decay_rate() -> f64 {
+    DEFAULT_SPIN_DECAY_RATE
+}
+
+impl Default for BallModel {
+    fn default() -> Self {
+        BallModel {
+            drag_scale: 1.0,
+            lift_scale: 1.0,
+            spin_decay_rate: DEFAULT_SPIN_DECAY_RATE,
+        }
+    }
+}
+
+impl BallModel {
+    /// Check that the scale factors are usable by the trajectory integrator
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(self.drag_scale.is_finite() && self.drag_scale > 0.0) {
+            return Err(ConfigError::invalid(DATA, "drag_scale must be positive"));
+        }
+        if !(self.lift_scale.is_finite() && self.lift_scale >= 0.0) {
+            return Err(ConfigError::invalid(
+                DATA,
+                "lift_scale must be non-negative",
+            ));
+        }
+        if !(self.spin_decay_rate.is_finite() && self.spin_decay_rate >= 0.0) {
+            return Err(ConfigError::invalid(
+                DATA,
+                "spin_decay_rate must be non-negative",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Serialize the model as TOML
+    pub fn to_toml_string(&self) -> String {
+        toml::to_string(self).expect("ball model always serializes")
+    }
+}
+
+/// Parse and validate a ball model from TOML text
+pub fn load_ball_model_from_str(toml_text: &str) -> Result<BallModel, ConfigError> {
+    let model: BallModel = parse_toml(DATA, toml_text)?;
+    model.validate()?;
+    Ok(model)
+}
+
+/// Parse and validate a ball model from a TOML file on disk
+pub fn load_ball_model_from_file(path: impl AsRef<Path>) -> Result<BallModel, ConfigError> {
+    load_file(path.as_ref(), load_ball_model_from_str)
+}
+
+/// Write a ball model to a TOML file on disk
+pub fn save_ball_model_to_file(
+    model: &BallModel,
+    path: impl AsRef<Path>,
+) -> Result<(), ConfigError> {
+    save_file(path.as_ref(), &model.to_toml_string())
+}
+
+fn model_lock() -> &'static RwLock<BallModel> {
+    BALL_MODEL.get_or_init(|| RwLock::new(BallModel::default()))
+}
+
+/// Ball model currently used by `calculate_trajectory`
+pub fn ball_model() -> BallModel {
+    *model_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Replace the ball model used by all subsequent trajectory calculations
+pub fn set_ball_model(model: BallModel) -> Result<(), ConfigError> {
+    model.validate()?;
+    *model_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = model;
+    Ok(())
+}
+
+/// Restore the uncalibrated ball model
+pub fn reset_ball_model() {
+    *model_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = BallModel::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_round_trip() {
+        let model = BallModel {
+            drag_scale: 1.07,
+            lift_scale: 0.93,
+            spin_decay_rate: 0.05,
+        };
+        let parsed = load_ball_model_from_str(&model.to_toml_string()).unwrap();
+        assert_eq!(parsed, model);
+    }
+
+    #[test]
+    fn test_spin_decay_defaults_when_missing() {
+        let model = load_ball_model_from_str("drag_scale = 1.1\nlift_scale = 0.9\n").unwrap();
+        assert_eq!(model.spin_decay_rate, DEFAULT_SPIN_DECAY_RATE);
+    }
+
+    #[test]
+    fn test_rejects_non_positive_drag() {
+        assert!(matches!(
+            load_ball_model_from_str("drag_scale = 0.0\nlift_scale = 1.0\n"),
+            Err(ConfigError::Invalid { .. })
+        ));
+    }
+}
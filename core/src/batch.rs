@@ -0,0 +1,320 @@
+use crate::bindings::prepare_input_data;
+use crate::{calculate_derived_values_from_input, DerivedValues, InputData};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Write;
+use std::thread;
+
+/// Settings for the batch entry points
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BatchOptions {
+    /// Worker threads; `None` uses the available parallelism (one thread on WebAssembly)
+    pub threads: Option<usize>,
+}
+
+impl BatchOptions {
+    fn thread_count(&self, items: usize) -> usize {
+        let threads = self.threads.unwrap_or_else(|| {
+            thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        });
+        threads.clamp(1, items.max(1))
+    }
+}
+
+/// Layout of a batch: one JSON array, or one JSON object per line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchFormat {
+    JsonArray,
+    JsonLines,
+}
+
+/// Why one shot of a batch could not be processed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchItemError {
+    /// Position of the shot in the batch, from 0
+    pub index: usize,
+
+    /// Line of the shot in JSON Lines input, from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+
+    pub message: String,
+}
+
+/// Processed batch, in input order
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResults {
+    pub format: BatchFormat,
+
+    /// Each shot's input object with `open_golf_coach` added, or why it failed
+    pub items: Vec<Result<Value, BatchItemError>>,
+}
+
+impl BatchResults {
+    pub fn error_count(&self) -> usize {
+        self.items.iter().filter(|item| item.is_err()).count()
+    }
+
+    /// Serialize in the input's layout; a failed shot becomes `{"error": {...}}` in its place
+    pub fn to_json_string(&self) -> String {
+        let item_json = |item: &Result<Value, BatchItemError>| match item {
+            Ok(value) => value.to_string(),
+            Err(error) => serde_json::json!({ "error": error }).to_string(),
+        };
+        match self.format {
+            BatchFormat::JsonArray => {
+                let items: Vec<String> = self.items.iter().map(item_json).collect();
+                format!("[{}]", items.join(","))
+            }
+            BatchFormat::JsonLines => {
+                let mut text = String::new();
+                for item in &self.items {
+                    let _ = writeln!(text, "{}", item_json(item));
+                }
+                text
+            }
+        }
+    }
+}
+
+/// Apply `f` to every item on up to `threads` scoped threads, keeping the input order
+fn parallel_map<T: Send, R: Send>(
+    items: Vec<T>,
+    threads: usize,
+    f: impl Fn(usize, T) -> R + Sync,
+) -> Vec<R> {
+    if threads <= 1 {
+        return items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| f(i, item))
+            .collect();
+    }
+    let chunk_size = items.len().div_ceil(threads);
+    let mut items = items.into_iter();
+    let chunks: Vec<Vec<T>> = (0..threads)
+        .map(|_| items.by_ref().take(chunk_size).collect())
+        .collect();
+    let f = &f;
+    thread::scope(|scope| {
+        let workers: Vec<_> = chunks
+            .into_iter()
+            .enumerate()
+            .map(|(chunk, slice)| {
+                scope.spawn(move || {
+                    let offset = chunk * chunk_size;
+                    slice
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, item)| f(offset + i, item))
+                        .collect::<Vec<R>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("batch worker panicked"))
+            .collect()
+    })
+}
+
+/// Calculate derived values for many parsed inputs in parallel, in input order. An input that
+/// fails validation is reported in its place and does not stop the rest.
+pub fn calculate_batch(
+    inputs: &[InputData],
+    options: &BatchOptions,
+) -> Vec<Result<DerivedValues, BatchItemError>> {
+    parallel_map(
+        inputs.iter().collect(),
+        options.thread_count(inputs.len()),
+        |index, input| {
+            input
+                .validate()
+                .map(|()| calculate_derived_values_from_input(input))
+                .map_err(|e| BatchItemError {
+                    index,
+                    line: None,
+                    message: format!("Invalid input format: {}", e),
+                })
+        },
+    )
+}
+
+/// Same as `calculate_derived_values` for one shot's JSON value
+fn process_value(mut value: Value) -> Result<Value, String> {
+    if !value.is_object() {
+        return Err("expected a JSON object".to_string());
+    }
+    let input = prepare_input_data(&value).map_err(|e| format!("Invalid input format: {}", e))?;
+    let derived = serde_json::to_value(calculate_derived_values_from_input(&input))
+        .map_err(|e| format!("Serialization error: {}", e))?;
+    if let Value::Object(map) = &mut value {
+        map.insert("open_golf_coach".to_string(), derived);
+    }
+    Ok(value)
+}
+
+/// Process shots given as JSON values in parallel; a shot that fails does not stop the rest
+pub fn calculate_batch_values(
+    inputs: Vec<Value>,
+    options: &BatchOptions,
+) -> Vec<Result<Value, BatchItemError>> {
+    let threads = options.thread_count(inputs.len());
+    parallel_map(inputs, threads, |index, value| {
+        process_value(value).map_err(|message| BatchItemError {
+            index,
+            line: None,
+            message,
+        })
+    })
+}
+
+/// Process a JSON array of shots, or JSON Lines (one shot per line, blank lines skipped).
+///
+/// Input starting with `[` is read as an array; anything else as JSON Lines. Malformed lines
+/// and invalid shots are reported per item; only a malformed array fails the whole batch.
+pub fn calculate_batch_json(
+    text: &str,
+    options: &BatchOptions,
+) -> serde_json::Result<BatchResults> {
+    if text.trim_start().starts_with('[') {
+        let inputs: Vec<Value> = serde_json::from_str(text)?;
+        return Ok(BatchResults {
+            format: BatchFormat::JsonArray,
+            items: calculate_batch_values(inputs, options),
+        });
+    }
+
+    let lines: Vec<(usize, &str)> = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, line))
+        .collect();
+    let threads = options.thread_count(lines.len());
+    let items = parallel_map(lines, threads, |index, (line, text)| {
+        serde_json::from_str(text)
+            .map_err(|e| format!("Parse error: {}", e))
+            .and_then(process_value)
+            .map_err(|message| BatchItemError {
+                index,
+                line: Some(line),
+                message,
+            })
+    });
+    Ok(BatchResults {
+        format: BatchFormat::JsonLines,
+        items,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shot_json(ball_speed: f64) -> String {
+        format!(
+            r#"{{"ball_speed_meters_per_second": {}, "vertical_launch_angle_degrees": 14.0, "total_spin_rpm": 4000.0}}"#,
+            ball_speed
+        )
+    }
+
+    fn carry(item: &Result<Value, BatchItemError>) -> f64 {
+        item.as_ref().unwrap()["open_golf_coach"]["carry_distance_meters"]
+            .as_f64()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_array_keeps_order_and_reports_bad_items() {
+        let speeds = [40.0, 50.0, 60.0, 70.0, 45.0, 55.0, 65.0];
+        let mut items: Vec<String> = speeds.iter().map(|&s| shot_json(s)).collect();
+        items.insert(3, r#"{"ball_speed_meters_per_second": "fast"}"#.to_string());
+        items.insert(5, "42".to_string());
+        let text = format!("[{}]", items.join(",\n"));
+
+        let threaded = calculate_batch_json(&text, &BatchOptions { threads: Some(3) }).unwrap();
+        let serial = calculate_batch_json(&text, &BatchOptions { threads: Some(1) }).unwrap();
+        assert_eq!(threaded, serial);
+        assert_eq!(threaded.format, BatchFormat::JsonArray);
+        assert_eq!(threaded.items.len(), 9);
+        assert_eq!(threaded.error_count(), 2);
+
+        let bad = threaded.items[3].as_ref().unwrap_err();
+        assert_eq!((bad.index, bad.line), (3, None));
+        assert!(bad.message.starts_with("Invalid input format"));
+        assert_eq!(threaded.items[5].as_ref().unwrap_err().index, 5);
+
+        // Faster shots carry farther, so order is visible in the results
+        assert!(carry(&threaded.items[0]) < carry(&threaded.items[1]));
+        assert!(carry(&threaded.items[6]) < carry(&threaded.items[4]));
+
+        let output: Value = serde_json::from_str(&threaded.to_json_string()).unwrap();
+        assert_eq!(output[5]["error"]["index"], 5);
+        assert_eq!(output[0]["ball_speed_meters_per_second"], 40.0);
+    }
+
+    #[test]
+    fn test_json_lines_with_line_numbers() {
+        let text = format!("{}\n\n{{not json\n{}\n", shot_json(50.0), shot_json(60.0));
+        let results = calculate_batch_json(&text, &BatchOptions::default()).unwrap();
+        assert_eq!(results.format, BatchFormat::JsonLines);
+        assert_eq!(results.items.len(), 3);
+
+        let bad = results.items[1].as_ref().unwrap_err();
+        assert_eq!((bad.index, bad.line), (1, Some(3)));
+        assert!(bad.message.starts_with("Parse error"));
+
+        let output = results.to_json_string();
+        assert_eq!(output.lines().count(), 3);
+        assert!(output.lines().nth(1).unwrap().starts_with(r#"{"error":"#));
+
+        assert!(calculate_batch_json("[1, 2", &BatchOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_zero_ball_speed_only_fails_itself() {
+        let text = format!(
+            "[{},{},{}]",
+            shot_json(50.0),
+            shot_json(0.0),
+            shot_json(60.0)
+        );
+        let results = calculate_batch_json(&text, &BatchOptions { threads: Some(2) }).unwrap();
+        assert_eq!(results.items.len(), 3);
+        assert_eq!(results.error_count(), 1);
+
+        let bad = results.items[1].as_ref().unwrap_err();
+        assert_eq!(bad.index, 1);
+        assert!(
+            bad.message.contains("ball speed must be positive"),
+            "{}",
+            bad.message
+        );
+        assert!(carry(&results.items[0]) < carry(&results.items[2]));
+    }
+
+    #[test]
+    fn test_slice_matches_single_calculation() {
+        let mut inputs: Vec<InputData> = (0..10)
+            .map(|i| serde_json::from_str(&shot_json(40.0 + i as f64)).unwrap())
+            .collect();
+        inputs[6] = serde_json::from_str(&shot_json(0.0)).unwrap();
+        let results = calculate_batch(&inputs, &BatchOptions { threads: Some(4) });
+        assert_eq!(results.len(), 10);
+        for (index, (input, result)) in inputs.iter().zip(&results).enumerate() {
+            if index == 6 {
+                assert_eq!(result.as_ref().unwrap_err().index, 6);
+                continue;
+            }
+            assert_eq!(
+                result.as_ref().unwrap().carry_distance_meters,
+                calculate_derived_values_from_input(input).carry_distance_meters
+            );
+        }
+    }
+}
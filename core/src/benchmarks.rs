@@ -0,0 +1,538 @@
+use crate::config_file::{load_file, parse_toml, ConfigError};
+use crate::session::SessionMetric;
+use crate::DerivedValues;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+static BENCHMARKS: OnceLock<RwLock<BenchmarkTables>> = OnceLock::new();
+
+const DATA: &str = "benchmarks";
+
+macro_rules! include_benchmarks {
+    () => {
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../shot_classification/benchmarks.toml"
+        ))
+    };
+}
+
+/// Average shot and spread for one club in one band
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClubBenchmark {
+    pub ball_speed_mps: f64,
+    pub ball_speed_sd_mps: f64,
+    pub launch_deg: f64,
+    pub launch_sd_deg: f64,
+    pub spin_rpm: f64,
+    pub spin_sd_rpm: f64,
+    pub carry_m: f64,
+    pub carry_sd_m: f64,
+    pub offline_sd_m: f64,
+}
+
+impl ClubBenchmark {
+    /// Mean and standard deviation for a compared metric
+    fn distribution(&self, metric: SessionMetric) -> Option<(f64, f64)> {
+        match metric {
+            SessionMetric::BallSpeedMetersPerSecond => {
+                Some((self.ball_speed_mps, self.ball_speed_sd_mps))
+            }
+            SessionMetric::VerticalLaunchAngleDegrees => {
+                Some((self.launch_deg, self.launch_sd_deg))
+            }
+            SessionMetric::TotalSpinRpm => Some((self.spin_rpm, self.spin_sd_rpm)),
+            SessionMetric::CarryDistanceMeters => Some((self.carry_m, self.carry_sd_m)),
+            _ => None,
+        }
+    }
+}
+
+/// Population of golfers shots are compared against
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BenchmarkBand {
+    pub name: String,
+
+    /// Multiplier on the tour expected-strokes baseline
+    pub strokes_scale: f64,
+
+    /// Keyed by normalized club name (see [`normalize_club`])
+    pub clubs: BTreeMap<String, ClubBenchmark>,
+}
+
+/// Benchmark bands plus the expected-strokes baseline used for strokes gained
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BenchmarkTables {
+    pub default_band: String,
+    pub green_radius_m: f64,
+
+    /// `[distance to the hole in meters, strokes]`, ascending
+    pub putting_strokes: Vec<[f64; 2]>,
+    pub approach_strokes: Vec<[f64; 2]>,
+
+    pub bands: BTreeMap<String, BenchmarkBand>,
+}
+
+/// Error raised when a comparison cannot be made
+#[derive(Debug)]
+pub enum BenchmarkError {
+    UnknownBand(String),
+    UnknownClub { band: String, club: String },
+    MissingClub(String),
+}
+
+impl fmt::Display for BenchmarkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BenchmarkError::UnknownBand(band) => write!(f, "Unknown benchmark band '{}'", band),
+            BenchmarkError::UnknownClub { band, club } => {
+                write!(f, "Benchmark band '{}' has no club '{}'", band, club)
+            }
+            BenchmarkError::MissingClub(msg) => write!(f, "Cannot pick a benchmark club: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for BenchmarkError {}
+
+/// Benchmark key for a club label: lowercase, no separators, "iron"/"wood"/"hybrid" shortened
+/// ("7 Iron" -> "7i", "3-Wood" -> "3w", "PW" -> "pw")
+pub fn normalize_club(club: &str) -> String {
+    let compact: String = club
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '-' | '_'))
+        .collect();
+    compact
+        .replace("iron", "i")
+        .replace("wood", "w")
+        .replace("hybrid", "h")
+}
+
+/// Linear interpolation in an ascending `[x, y]` table; flat below the first row, extrapolated
+/// past the last one
+fn interpolate(table: &[[f64; 2]], x: f64) -> f64 {
+    if x <= table[0][0] {
+        return table[0][1];
+    }
+    let segment = table
+        .windows(2)
+        .find(|pair| x <= pair[1][0])
+        .unwrap_or(&table[table.len() - 2..]);
+    let ([x0, y0], [x1, y1]) = (segment[0], segment[1]);
+    y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+}
+
+impl BenchmarkTables {
+    /// Check band references, table ordering and spreads
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |msg: String| Err(ConfigError::invalid(DATA, msg));
+        if !self.bands.contains_key(&self.default_band) {
+            return invalid(format!(
+                "default_band '{}' is not defined",
+                self.default_band
+            ));
+        }
+        if self.green_radius_m <= 0.0 {
+            return invalid("green_radius_m must be positive".to_string());
+        }
+        for (name, table) in [
+            ("putting_strokes", &self.putting_strokes),
+            ("approach_strokes", &self.approach_strokes),
+        ] {
+            if table.len() < 2 {
+                return invalid(format!("{} needs at least two rows", name));
+            }
+            if table.windows(2).any(|pair| pair[1][0] <= pair[0][0]) {
+                return invalid(format!("{} distances must increase", name));
+            }
+        }
+        for (key, band) in &self.bands {
+            if band.strokes_scale <= 0.0 {
+                return invalid(format!("band '{}': strokes_scale must be positive", key));
+            }
+            for (club, bench) in &band.clubs {
+                if *club != normalize_club(club) {
+                    return invalid(format!(
+                        "band '{}': club key '{}' should be written '{}'",
+                        key,
+                        club,
+                        normalize_club(club)
+                    ));
+                }
+                let spreads = [
+                    bench.ball_speed_sd_mps,
+                    bench.launch_sd_deg,
+                    bench.spin_sd_rpm,
+                    bench.carry_sd_m,
+                    bench.offline_sd_m,
+                ];
+                if spreads.iter().any(|sd| *sd <= 0.0) {
+                    return invalid(format!(
+                        "band '{}', club '{}': standard deviations must be positive",
+                        key, club
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn band(&self, key: &str) -> Result<&BenchmarkBand, BenchmarkError> {
+        self.bands
+            .get(key)
+            .ok_or_else(|| BenchmarkError::UnknownBand(key.to_string()))
+    }
+
+    /// Strokes a tour player needs to hole out from `distance_m`, scaled for a band; a ball
+    /// already in the hole (0 m) needs none
+    pub fn expected_strokes(&self, band: &BenchmarkBand, distance_m: f64) -> f64 {
+        if distance_m <= 0.0 {
+            return 0.0;
+        }
+        let table = if distance_m <= self.green_radius_m {
+            &self.putting_strokes
+        } else {
+            &self.approach_strokes
+        };
+        interpolate(table, distance_m) * band.strokes_scale
+    }
+
+    /// Strokes gained by an approach from `start_m` that finished `finish_m` from the hole;
+    /// positive = better than the band's average player
+    pub fn strokes_gained(&self, band: &BenchmarkBand, start_m: f64, finish_m: f64) -> f64 {
+        self.expected_strokes(band, start_m) - self.expected_strokes(band, finish_m) - 1.0
+    }
+}
+
+/// One metric measured against a band
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricComparison {
+    /// The shot's value, or the mean over the compared shots
+    pub value: f64,
+    pub band_mean: f64,
+    pub band_standard_deviation: f64,
+
+    /// Share (0-100) of the band's shots below `value`
+    pub percentile: f64,
+}
+
+/// Shots with one club measured against a benchmark band
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkComparison {
+    pub band: String,
+    pub band_name: String,
+    pub club: String,
+    pub shot_count: usize,
+
+    /// Ball speed, launch, spin and carry, when the shots report them
+    pub metrics: BTreeMap<SessionMetric, MetricComparison>,
+
+    /// Share (0-100) of the band's shots that finish farther from the target line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accuracy_percentile: Option<f64>,
+
+    /// Average strokes gained per shot with a `target_distance_meters`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strokes_gained: Option<f64>,
+
+    pub strokes_gained_shots: usize,
+}
+
+/// Compare shots hit with one club against a band of the current benchmarks.
+///
+/// `band` defaults to the tables' `default_band`. Without `club`, the shots' own `club` is
+/// used and must be the same for all of them; with it, shots tagged with another club are
+/// skipped. Strokes gained needs `target_distance_meters` on the shot.
+pub fn compare_to_benchmark(
+    shots: &[DerivedValues],
+    band: Option<&str>,
+    club: Option<&str>,
+) -> Result<BenchmarkComparison, BenchmarkError> {
+    let tables = benchmarks_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    compare_with_tables(&tables, shots, band, club)
+}
+
+fn compare_with_tables(
+    tables: &BenchmarkTables,
+    shots: &[DerivedValues],
+    band: Option<&str>,
+    club: Option<&str>,
+) -> Result<BenchmarkComparison, BenchmarkError> {
+    let band_key = band.unwrap_or(&tables.default_band);
+    let band = tables.band(band_key)?;
+    let club = match club {
+        Some(club) => normalize_club(club),
+        None => {
+            let mut clubs: Vec<String> = shots
+                .iter()
+                .filter_map(|shot| shot.club.as_deref().map(normalize_club))
+                .collect();
+            clubs.sort();
+            clubs.dedup();
+            match clubs.as_slice() {
+                [club] => club.clone(),
+                [] => {
+                    return Err(BenchmarkError::MissingClub(
+                        "no shot has a club".to_string(),
+                    ))
+                }
+                _ => {
+                    return Err(BenchmarkError::MissingClub(format!(
+                        "shots use several clubs ({})",
+                        clubs.join(", ")
+                    )))
+                }
+            }
+        }
+    };
+    let bench = band
+        .clubs
+        .get(&club)
+        .ok_or_else(|| BenchmarkError::UnknownClub {
+            band: band_key.to_string(),
+            club: club.clone(),
+        })?;
+
+    let shots: Vec<&DerivedValues> = shots
+        .iter()
+        .filter(|shot| {
+            shot.club
+                .as_deref()
+                .is_none_or(|c| normalize_club(c) == club)
+        })
+        .collect();
+
+    let metrics = [
+        SessionMetric::BallSpeedMetersPerSecond,
+        SessionMetric::VerticalLaunchAngleDegrees,
+        SessionMetric::TotalSpinRpm,
+        SessionMetric::CarryDistanceMeters,
+    ]
+    .into_iter()
+    .filter_map(|metric| {
+        let (mean, sd) = bench.distribution(metric)?;
+        let value = average(shots.iter().filter_map(|shot| metric.value(shot)))?;
+        Some((
+            metric,
+            MetricComparison {
+                value,
+                band_mean: mean,
+                band_standard_deviation: sd,
+                percentile: 100.0 * normal_cdf((value - mean) / sd),
+            },
+        ))
+    })
+    .collect();
+
+    // Two-sided: the share of the band missing the target line by more
+    let accuracy_percentile = average(shots.iter().filter_map(|shot| {
+        let offline = shot.offline_distance_meters?.abs();
+        Some(100.0 * 2.0 * (1.0 - normal_cdf(offline / bench.offline_sd_m)))
+    }));
+
+    let gains: Vec<f64> = shots
+        .iter()
+        .filter_map(|shot| {
+            let start = shot.target_distance_meters?;
+            let finish = shot.distance_to_target_meters?;
+            Some(tables.strokes_gained(band, start, finish))
+        })
+        .collect();
+
+    Ok(BenchmarkComparison {
+        band: band_key.to_string(),
+        band_name: band.name.clone(),
+        club,
+        shot_count: shots.len(),
+        metrics,
+        accuracy_percentile,
+        strokes_gained: average(gains.iter().copied()),
+        strokes_gained_shots: gains.len(),
+    })
+}
+
+fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values
+        .filter(|v| v.is_finite())
+        .fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// Standard normal CDF (Abramowitz & Stegun 7.1.26, error below 1.5e-7)
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+/// Parse and validate benchmark tables from TOML text (layout of `shot_classification/benchmarks.toml`)
+pub fn load_benchmarks_from_str(toml_text: &str) -> Result<BenchmarkTables, ConfigError> {
+    let tables: BenchmarkTables = parse_toml(DATA, toml_text)?;
+    tables.validate()?;
+    Ok(tables)
+}
+
+/// Parse and validate benchmark tables from a TOML file on disk
+pub fn load_benchmarks_from_file(path: impl AsRef<Path>) -> Result<BenchmarkTables, ConfigError> {
+    load_file(path.as_ref(), load_benchmarks_from_str)
+}
+
+/// Benchmark tables shipped with the library
+pub fn default_benchmarks() -> BenchmarkTables {
+    load_benchmarks_from_str(include_benchmarks!()).expect("embedded benchmarks.toml must be valid")
+}
+
+fn benchmarks_lock() -> &'static RwLock<BenchmarkTables> {
+    BENCHMARKS.get_or_init(|| RwLock::new(default_benchmarks()))
+}
+
+/// A club's benchmark from the band whose average ball speed with it is closest to
+/// `ball_speed_mps`, so a shot is judged against players who swing like it
+pub(crate) fn club_benchmark_for_speed(club: &str, ball_speed_mps: f64) -> Option<ClubBenchmark> {
+    let club = normalize_club(club);
+    let tables = benchmarks_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    tables
+        .bands
+        .values()
+        .filter_map(|band| band.clubs.get(&club))
+        .min_by(|a, b| {
+            let gap = |row: &ClubBenchmark| (row.ball_speed_mps - ball_speed_mps).abs();
+            gap(a).total_cmp(&gap(b))
+        })
+        .copied()
+}
+
+/// Benchmark tables currently used by [`compare_to_benchmark`]
+pub fn benchmarks() -> BenchmarkTables {
+    benchmarks_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Replace the benchmark tables used by all subsequent comparisons
+pub fn set_benchmarks(tables: BenchmarkTables) -> Result<(), ConfigError> {
+    tables.validate()?;
+    *benchmarks_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = tables;
+    Ok(())
+}
+
+/// Restore the benchmark tables shipped with the library
+pub fn reset_benchmarks() {
+    *benchmarks_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = default_benchmarks();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seven_iron(ball_speed: f64, carry: f64, offline: f64) -> DerivedValues {
+        let mut shot = DerivedValues::new();
+        shot.club = Some("7 Iron".to_string());
+        shot.ball_speed_meters_per_second = Some(ball_speed);
+        shot.carry_distance_meters = Some(carry);
+        shot.offline_distance_meters = Some(offline);
+        shot
+    }
+
+    #[test]
+    fn test_default_benchmarks_cover_every_band() {
+        let tables = default_benchmarks();
+        assert_eq!(tables.default_band, "mid_handicap");
+        for band in tables.bands.values() {
+            assert_eq!(band.clubs.len(), 10, "{}", band.name);
+        }
+        assert_eq!(normalize_club("3-Wood"), "3w");
+        assert_eq!(normalize_club(" PW "), "pw");
+    }
+
+    #[test]
+    fn test_percentiles_against_band() {
+        let tables = default_benchmarks();
+        let tour = tables.bands["tour"].clubs["7i"];
+        let shot = seven_iron(tour.ball_speed_mps, tour.carry_m + tour.carry_sd_m, 0.0);
+
+        let result =
+            compare_with_tables(&tables, std::slice::from_ref(&shot), Some("tour"), None).unwrap();
+        assert_eq!(result.club, "7i");
+        let speed = result.metrics[&SessionMetric::BallSpeedMetersPerSecond];
+        assert!((speed.percentile - 50.0).abs() < 1e-6);
+        let carry = result.metrics[&SessionMetric::CarryDistanceMeters];
+        assert!((carry.percentile - 84.13).abs() < 0.01);
+        assert!((result.accuracy_percentile.unwrap() - 100.0).abs() < 1e-6);
+        assert!(!result.metrics.contains_key(&SessionMetric::TotalSpinRpm));
+
+        // The same shot ranks higher against high handicappers
+        let high = compare_with_tables(&tables, &[shot], Some("high_handicap"), None).unwrap();
+        assert!(high.metrics[&SessionMetric::CarryDistanceMeters].percentile > 99.0);
+    }
+
+    #[test]
+    fn test_strokes_gained_from_proximity() {
+        let tables = default_benchmarks();
+        let tour = &tables.bands["tour"];
+        // 145 m to a tour-average 2.98; finishing 6 m away leaves 1.87
+        assert!((tables.strokes_gained(tour, 145.0, 6.0) - 0.11).abs() < 1e-9);
+        assert!(tables.strokes_gained(tour, 145.0, 30.0) < 0.0);
+        // Holed out: the approach saved every stroke a tour player would still need
+        assert!((tables.strokes_gained(tour, 145.0, 0.0) - 1.98).abs() < 1e-9);
+
+        let mut close = seven_iron(53.0, 140.0, 2.0);
+        close.target_distance_meters = Some(145.0);
+        close.distance_to_target_meters = Some(6.0);
+        let mut untargeted = seven_iron(53.0, 140.0, 2.0);
+        untargeted.club = None;
+        let result =
+            compare_with_tables(&tables, &[close, untargeted], Some("tour"), Some("7i")).unwrap();
+        assert_eq!((result.shot_count, result.strokes_gained_shots), (2, 1));
+        assert!((result.strokes_gained.unwrap() - 0.11).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_comparison_errors() {
+        let tables = default_benchmarks();
+        let shots = [seven_iron(50.0, 130.0, 0.0)];
+        assert!(matches!(
+            compare_with_tables(&tables, &shots, Some("pro_am"), None),
+            Err(BenchmarkError::UnknownBand(_))
+        ));
+        assert!(matches!(
+            compare_with_tables(&tables, &shots, None, Some("2 iron")),
+            Err(BenchmarkError::UnknownClub { .. })
+        ));
+        assert!(matches!(
+            compare_with_tables(&tables, &[DerivedValues::new()], None, None),
+            Err(BenchmarkError::MissingClub(_))
+        ));
+
+        let text = include_benchmarks!()
+            .replace("default_band = \"mid_handicap\"", "default_band = \"x\"");
+        assert!(matches!(
+            load_benchmarks_from_str(&text),
+            Err(ConfigError::Invalid { .. })
+        ));
+    }
+}
@@ -1,23 +1,30 @@
+use crate::batch::{calculate_batch_json, BatchOptions};
 use crate::{calculate_derived_values_from_input, InputData};
+use serde::Deserialize;
 use serde_json::Value;
 use wasm_bindgen::prelude::*;
 
 fn merge_existing_derived_fields(value: &mut Value) {
     if let Value::Object(map) = value {
-        if let Some(open_value) = map.get("open_golf_coach").cloned() {
-            if let Value::Object(open_map) = open_value {
-                for (key, val) in open_map {
-                    map.entry(key).or_insert(val);
-                }
+        if let Some(Value::Object(open_map)) = map.get("open_golf_coach").cloned() {
+            for (key, val) in open_map {
+                map.entry(key).or_insert(val);
             }
         }
     }
 }
 
-fn prepare_input_data(value: &Value) -> Result<InputData, serde_json::Error> {
-    let mut merged = value.clone();
-    merge_existing_derived_fields(&mut merged);
-    serde_json::from_value(merged)
+/// Parse and validate one shot; only a shot carrying earlier `open_golf_coach` results is copied
+pub(crate) fn prepare_input_data(value: &Value) -> Result<InputData, serde_json::Error> {
+    let input = if value.get("open_golf_coach").is_some_and(Value::is_object) {
+        let mut merged = value.clone();
+        merge_existing_derived_fields(&mut merged);
+        InputData::deserialize(merged)?
+    } else {
+        InputData::deserialize(value)?
+    };
+    input.validate().map_err(serde::de::Error::custom)?;
+    Ok(input)
 }
 
 /// WebAssembly binding for JavaScript/TypeScript
@@ -49,8 +56,21 @@ pub fn calculate_derived_values(json_input: &str) -> Result<String, JsValue> {
         .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
 }
 
+/// WebAssembly binding for many shots at once: a JSON array or JSON Lines in, the same
+/// layout out, with `{"error": {...}}` in place of each shot that failed
+#[wasm_bindgen]
+pub fn calculate_derived_values_batch(json_input: &str) -> Result<String, JsValue> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    calculate_batch_json(json_input, &BatchOptions::default())
+        .map(|results| results.to_json_string())
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))
+}
+
 /// C-compatible FFI function for C++/Unity/Unreal
 #[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub extern "C" fn calculate_derived_values_ffi(
     json_input: *const std::os::raw::c_char,
     output_buffer: *mut std::os::raw::c_char,
@@ -0,0 +1,391 @@
+use crate::ball_model::{self, BallModel};
+use crate::calculate_spin_components;
+use crate::config_file::ConfigError;
+use crate::trajectory::calculate_trajectory_with_model;
+use crate::trajectory_analysis::{get_carry_distance, get_peak_height};
+use serde::{Deserialize, Serialize};
+
+/// Calibration search constants
+const MIN_SCALE: f64 = 0.5;
+const MAX_SCALE: f64 = 2.0;
+const STEP_SHRINK: f64 = 0.5;
+
+/// A launch-monitor shot with measured flight results, used to fit a ball model
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CalibrationShot {
+    pub ball_speed_meters_per_second: f64,
+    pub vertical_launch_angle_degrees: f64,
+
+    #[serde(default)]
+    pub horizontal_launch_angle_degrees: f64,
+
+    pub total_spin_rpm: f64,
+
+    #[serde(default)]
+    pub spin_axis_degrees: f64,
+
+    pub measured_carry_distance_meters: f64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measured_peak_height_meters: Option<f64>,
+
+    #[serde(default)]
+    pub elevation_meters: f64,
+
+    #[serde(default = "default_temperature_kelvin")]
+    pub temperature_kelvin: f64,
+
+    #[serde(default = "default_humidity_percent")]
+    pub humidity_percent: f64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pressure_pascals: Option<f64>,
+}
+
+fn default_temperature_kelvin() -> f64 {
+    298.15 // 25°C, same default as calculate_derived_values
+}
+
+fn default_humidity_percent() -> f64 {
+    50.0
+}
+
+impl CalibrationShot {
+    fn is_usable(&self) -> bool {
+        self.ball_speed_meters_per_second.is_finite()
+            && self.ball_speed_meters_per_second > 0.0
+            && self.vertical_launch_angle_degrees.is_finite()
+            && self.vertical_launch_angle_degrees > 0.0
+            && self.total_spin_rpm.is_finite()
+            && self.measured_carry_distance_meters.is_finite()
+            && self.measured_carry_distance_meters > 0.0
+    }
+}
+
+/// Settings for [`calibrate_ball_model`]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CalibrationOptions {
+    /// Model the search starts from; its spin decay rate is kept as-is
+    pub initial_model: BallModel,
+
+    /// Weight of squared peak-height error relative to squared carry error
+    pub peak_height_weight: f64,
+
+    /// First step size for the drag and lift scale search; must be positive
+    pub initial_step: f64,
+
+    /// Search stops once the step size falls below this; must be positive
+    pub tolerance: f64,
+
+    /// Upper bound on candidate models tried, including those clamped back onto the current
+    /// best (each evaluation simulates every shot)
+    pub max_evaluations: usize,
+}
+
+impl Default for CalibrationOptions {
+    fn default() -> Self {
+        CalibrationOptions {
+            initial_model: BallModel::default(),
+            peak_height_weight: 1.0,
+            initial_step: 0.1,
+            tolerance: 0.002,
+            max_evaluations: 200,
+        }
+    }
+}
+
+/// Residual statistics (predicted minus measured) for one flight metric
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResidualStats {
+    pub count: usize,
+
+    /// Mean signed error; positive means the model flies the ball too far / too high
+    pub mean_error_meters: f64,
+
+    pub mean_absolute_error_meters: f64,
+    pub rms_error_meters: f64,
+    pub max_absolute_error_meters: f64,
+}
+
+impl ResidualStats {
+    fn from_residuals(residuals: &[f64]) -> Option<Self> {
+        if residuals.is_empty() {
+            return None;
+        }
+        let n = residuals.len() as f64;
+        Some(ResidualStats {
+            count: residuals.len(),
+            mean_error_meters: residuals.iter().sum::<f64>() / n,
+            mean_absolute_error_meters: residuals.iter().map(|r| r.abs()).sum::<f64>() / n,
+            rms_error_meters: (residuals.iter().map(|r| r * r).sum::<f64>() / n).sqrt(),
+            max_absolute_error_meters: residuals.iter().fold(0.0, |max, r| r.abs().max(max)),
+        })
+    }
+}
+
+/// Fitted ball model with fit quality before and after calibration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationResult {
+    pub model: BallModel,
+    pub carry_residuals_before: ResidualStats,
+    pub carry_residuals_after: ResidualStats,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_height_residuals_before: Option<ResidualStats>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_height_residuals_after: Option<ResidualStats>,
+
+    pub shots_used: usize,
+    pub shots_skipped: usize,
+    pub evaluations: usize,
+}
+
+/// Carry and peak-height residuals of `model` over `shots`
+fn residuals(shots: &[CalibrationShot], model: &BallModel) -> (Vec<f64>, Vec<f64>) {
+    let mut carry = Vec::with_capacity(shots.len());
+    let mut peak = Vec::new();
+
+    for shot in shots {
+        let (backspin, sidespin) =
+            calculate_spin_components(shot.total_spin_rpm, shot.spin_axis_degrees);
+        let trajectory = calculate_trajectory_with_model(
+            shot.ball_speed_meters_per_second,
+            shot.vertical_launch_angle_degrees,
+            shot.horizontal_launch_angle_degrees,
+            backspin,
+            sidespin,
+            shot.elevation_meters,
+            shot.temperature_kelvin,
+            shot.humidity_percent,
+            shot.pressure_pascals,
+            model,
+        );
+
+        carry.push(get_carry_distance(&trajectory) - shot.measured_carry_distance_meters);
+        if let Some(measured_peak) = shot.measured_peak_height_meters {
+            peak.push(get_peak_height(&trajectory) - measured_peak);
+        }
+    }
+
+    (carry, peak)
+}
+
+/// Mean squared error combining carry and weighted peak height
+fn objective(carry: &[f64], peak: &[f64], peak_height_weight: f64) -> f64 {
+    let carry_sq: f64 = carry.iter().map(|r| r * r).sum();
+    let peak_sq: f64 = peak.iter().map(|r| r * r).sum();
+    let total = carry_sq + peak_height_weight * peak_sq;
+    if total.is_finite() {
+        total / carry.len() as f64
+    } else {
+        f64::INFINITY
+    }
+}
+
+/// Fit drag and lift scale factors to measured carries (and peak heights, when present).
+///
+/// Runs a bounded compass search over `drag_scale` and `lift_scale`, minimising the mean
+/// squared carry error plus `peak_height_weight` times the mean squared peak-height error.
+/// Shots with missing or non-physical launch data are skipped.
+///
+/// # Arguments
+/// * `shots` - Launch-monitor shots with measured carry distance
+/// * `options` - Starting model, error weighting and search settings
+///
+/// # Returns
+/// The fitted model and residual statistics, or an error if no shot is usable
+pub fn calibrate_ball_model(
+    shots: &[CalibrationShot],
+    options: &CalibrationOptions,
+) -> Result<CalibrationResult, ConfigError> {
+    options.initial_model.validate()?;
+    for (name, value) in [
+        ("initial_step", options.initial_step),
+        ("tolerance", options.tolerance),
+    ] {
+        if !value.is_finite() || value <= 0.0 {
+            return Err(ConfigError::invalid(
+                ball_model::DATA,
+                format!("{} must be a positive number, got {}", name, value),
+            ));
+        }
+    }
+    let usable: Vec<CalibrationShot> = shots.iter().copied().filter(|s| s.is_usable()).collect();
+    if usable.is_empty() {
+        return Err(ConfigError::invalid(
+            ball_model::DATA,
+            "no usable calibration shots",
+        ));
+    }
+
+    let weight = options.peak_height_weight.max(0.0);
+    let (carry_before, peak_before) = residuals(&usable, &options.initial_model);
+    let mut best_model = options.initial_model;
+    let mut best_error = objective(&carry_before, &peak_before, weight);
+    let mut best_residuals = (carry_before.clone(), peak_before.clone());
+    let mut evaluations = 1;
+    let mut tried = 1;
+    let mut step = options.initial_step;
+
+    'search: while step >= options.tolerance {
+        let mut improved = false;
+
+        for (d_drag, d_lift) in [(step, 0.0), (-step, 0.0), (0.0, step), (0.0, -step)] {
+            if tried >= options.max_evaluations {
+                break 'search;
+            }
+            tried += 1;
+
+            let candidate = BallModel {
+                drag_scale: (best_model.drag_scale + d_drag).clamp(MIN_SCALE, MAX_SCALE),
+                lift_scale: (best_model.lift_scale + d_lift).clamp(MIN_SCALE, MAX_SCALE),
+                ..best_model
+            };
+            if candidate == best_model {
+                continue;
+            }
+
+            let (carry, peak) = residuals(&usable, &candidate);
+            let error = objective(&carry, &peak, weight);
+            evaluations += 1;
+
+            if error < best_error {
+                best_model = candidate;
+                best_error = error;
+                best_residuals = (carry, peak);
+                improved = true;
+                break;
+            }
+        }
+
+        if !improved {
+            step *= STEP_SHRINK;
+        }
+    }
+
+    let (carry_after, peak_after) = best_residuals;
+
+    Ok(CalibrationResult {
+        model: best_model,
+        carry_residuals_before: ResidualStats::from_residuals(&carry_before)
+            .expect("at least one usable shot"),
+        carry_residuals_after: ResidualStats::from_residuals(&carry_after)
+            .expect("at least one usable shot"),
+        peak_height_residuals_before: ResidualStats::from_residuals(&peak_before),
+        peak_height_residuals_after: ResidualStats::from_residuals(&peak_after),
+        shots_used: usable.len(),
+        shots_skipped: shots.len() - usable.len(),
+        evaluations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shots "measured" with a known model, so calibration should recover it
+    fn synthetic_shots(model: &BallModel) -> Vec<CalibrationShot> {
+        let launches = [
+            (70.0, 11.0, 2500.0),
+            (60.0, 14.0, 4500.0),
+            (52.0, 18.0, 6500.0),
+            (42.0, 25.0, 8500.0),
+        ];
+
+        launches
+            .iter()
+            .map(|&(speed, vla, spin)| {
+                let (backspin, sidespin) = calculate_spin_components(spin, 0.0);
+                let trajectory = calculate_trajectory_with_model(
+                    speed, vla, 0.0, backspin, sidespin, 0.0, 298.15, 50.0, None, model,
+                );
+                CalibrationShot {
+                    ball_speed_meters_per_second: speed,
+                    vertical_launch_angle_degrees: vla,
+                    horizontal_launch_angle_degrees: 0.0,
+                    total_spin_rpm: spin,
+                    spin_axis_degrees: 0.0,
+                    measured_carry_distance_meters: get_carry_distance(&trajectory),
+                    measured_peak_height_meters: Some(get_peak_height(&trajectory)),
+                    elevation_meters: 0.0,
+                    temperature_kelvin: 298.15,
+                    humidity_percent: 50.0,
+                    pressure_pascals: None,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recovers_known_scales() {
+        let truth = BallModel {
+            drag_scale: 1.12,
+            lift_scale: 0.9,
+            ..BallModel::default()
+        };
+        let result =
+            calibrate_ball_model(&synthetic_shots(&truth), &CalibrationOptions::default()).unwrap();
+
+        assert!(
+            (result.model.drag_scale - truth.drag_scale).abs() < 0.03,
+            "drag scale {}",
+            result.model.drag_scale
+        );
+        assert!(
+            (result.model.lift_scale - truth.lift_scale).abs() < 0.03,
+            "lift scale {}",
+            result.model.lift_scale
+        );
+        assert!(
+            result.carry_residuals_after.rms_error_meters
+                < result.carry_residuals_before.rms_error_meters / 5.0
+        );
+        assert!(result.peak_height_residuals_after.is_some());
+    }
+
+    #[test]
+    fn test_skips_unusable_shots() {
+        let mut shots = synthetic_shots(&BallModel::default());
+        shots[0].measured_carry_distance_meters = f64::NAN;
+        let result = calibrate_ball_model(&shots, &CalibrationOptions::default()).unwrap();
+        assert_eq!(result.shots_used, 3);
+        assert_eq!(result.shots_skipped, 1);
+        assert!(result.carry_residuals_before.rms_error_meters < 0.5);
+    }
+
+    #[test]
+    fn test_rejects_empty_dataset() {
+        assert!(calibrate_ball_model(&[], &CalibrationOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_positive_search_settings() {
+        let shots = synthetic_shots(&BallModel::default());
+        for options in [
+            CalibrationOptions {
+                tolerance: 0.0,
+                ..Default::default()
+            },
+            CalibrationOptions {
+                initial_step: f64::NAN,
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                calibrate_ball_model(&shots, &options),
+                Err(ConfigError::Invalid { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_residual_stats() {
+        let stats = ResidualStats::from_residuals(&[1.0, -3.0]).unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.mean_error_meters, -1.0);
+        assert_eq!(stats.mean_absolute_error_meters, 2.0);
+        assert_eq!(stats.max_absolute_error_meters, 3.0);
+        assert!((stats.rms_error_meters - 5.0_f64.sqrt()).abs() < 1e-12);
+    }
+}
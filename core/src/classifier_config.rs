@@ -0,0 +1,258 @@
+use crate::config_file::{load_file, parse_toml, ConfigError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+static CLASSIFIER_PRESETS: OnceLock<ClassifierPresetsFile> = OnceLock::new();
+static CLASSIFIER_CONFIG: OnceLock<RwLock<ClassifierConfig>> = OnceLock::new();
+
+const DATA: &str = "classifier config";
+
+macro_rules! include_classifier_config {
+    () => {
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../shot_classification/classifier_config.toml"
+        ))
+    };
+}
+
+/// Player skill level used to pick a classification preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillLevel {
+    Beginner,
+    Intermediate,
+    Tour,
+}
+
+/// Thresholds used by the shot classifier
+///
+/// Direction and shape thresholds are magnitudes applied symmetrically left and right.
+/// Fields missing from a TOML document fall back to the original classifier thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClassifierConfig {
+    /// Largest |HLA| still called straight (beyond it: push/pull)
+    pub straight_max_hla_degrees: f64,
+    /// Smallest |spin axis| that counts as draw/fade
+    pub curve_min_spin_axis_degrees: f64,
+    /// Smallest |spin axis| that counts as hook/slice
+    pub severe_curve_min_spin_axis_degrees: f64,
+
+    pub putt_max_vla_degrees: f64,
+    pub putt_max_ball_speed_mps: f64,
+
+    pub worm_burner_max_vla_degrees: f64,
+    pub worm_burner_min_ball_speed_mps: f64,
+
+    pub shank_min_hla_degrees: f64,
+    pub shank_min_vla_degrees: f64,
+
+    pub duck_hook_min_ball_speed_mps: f64,
+    pub duck_hook_max_vla_degrees: f64,
+    pub duck_hook_min_spin_axis_degrees: f64,
+
+    pub banana_slice_min_ball_speed_mps: f64,
+    pub banana_slice_min_vla_degrees: f64,
+    pub banana_slice_min_spin_axis_degrees: f64,
+
+    /// Largest |HLA| and |spin axis| for Baby Push Draw / Baby Pull Fade
+    pub baby_shot_max_degrees: f64,
+
+    /// How far past a direction/shape boundary a shot may land and keep the previous
+    /// shot's label (only applied when the previous label is supplied)
+    pub hysteresis_degrees: f64,
+}
+
+impl Default for ClassifierConfig {
+    fn default() -> Self {
+        ClassifierConfig {
+            straight_max_hla_degrees: 3.0,
+            curve_min_spin_axis_degrees: 3.0,
+            severe_curve_min_spin_axis_degrees: 12.0,
+            putt_max_vla_degrees: 0.1,
+            putt_max_ball_speed_mps: 15.0,
+            worm_burner_max_vla_degrees: 5.0,
+            worm_burner_min_ball_speed_mps: 20.0,
+            shank_min_hla_degrees: 12.0,
+            shank_min_vla_degrees: 12.0,
+            duck_hook_min_ball_speed_mps: 30.0,
+            duck_hook_max_vla_degrees: 15.0,
+            duck_hook_min_spin_axis_degrees: 25.0,
+            banana_slice_min_ball_speed_mps: 30.0,
+            banana_slice_min_vla_degrees: 20.0,
+            banana_slice_min_spin_axis_degrees: 25.0,
+            baby_shot_max_degrees: 2.0,
+            hysteresis_degrees: 0.5,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ClassifierPresetsFile {
+    default_skill_level: SkillLevel,
+    presets: SkillPresets,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SkillPresets {
+    beginner: ClassifierConfig,
+    intermediate: ClassifierConfig,
+    tour: ClassifierConfig,
+}
+
+impl ClassifierConfig {
+    /// Preset shipped with the library for a skill level
+    pub fn for_skill_level(skill_level: SkillLevel) -> Self {
+        let presets = &embedded_presets().presets;
+        match skill_level {
+            SkillLevel::Beginner => presets.beginner,
+            SkillLevel::Intermediate => presets.intermediate,
+            SkillLevel::Tour => presets.tour,
+        }
+    }
+
+    /// Check that every threshold is a finite, non-negative number and the curve bands nest
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let values = [
+            self.straight_max_hla_degrees,
+            self.curve_min_spin_axis_degrees,
+            self.severe_curve_min_spin_axis_degrees,
+            self.putt_max_vla_degrees,
+            self.putt_max_ball_speed_mps,
+            self.worm_burner_max_vla_degrees,
+            self.worm_burner_min_ball_speed_mps,
+            self.shank_min_hla_degrees,
+            self.shank_min_vla_degrees,
+            self.duck_hook_min_ball_speed_mps,
+            self.duck_hook_max_vla_degrees,
+            self.duck_hook_min_spin_axis_degrees,
+            self.banana_slice_min_ball_speed_mps,
+            self.banana_slice_min_vla_degrees,
+            self.banana_slice_min_spin_axis_degrees,
+            self.baby_shot_max_degrees,
+            self.hysteresis_degrees,
+        ];
+        if values.iter().any(|v| !v.is_finite() || *v < 0.0) {
+            return Err(ConfigError::invalid(
+                DATA,
+                "thresholds must be finite and non-negative",
+            ));
+        }
+        if self.severe_curve_min_spin_axis_degrees <= self.curve_min_spin_axis_degrees {
+            return Err(ConfigError::invalid(
+                DATA,
+                "severe_curve_min_spin_axis_degrees must exceed curve_min_spin_axis_degrees",
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn embedded_presets() -> &'static ClassifierPresetsFile {
+    CLASSIFIER_PRESETS.get_or_init(|| {
+        let file: ClassifierPresetsFile = toml::from_str(include_classifier_config!())
+            .expect("embedded classifier_config.toml must be valid");
+        for preset in [
+            &file.presets.beginner,
+            &file.presets.intermediate,
+            &file.presets.tour,
+        ] {
+            preset
+                .validate()
+                .expect("embedded classifier presets must be valid");
+        }
+        file
+    })
+}
+
+/// Parse and validate a classifier config from TOML text (flat threshold keys)
+pub fn load_classifier_config_from_str(toml_text: &str) -> Result<ClassifierConfig, ConfigError> {
+    let config: ClassifierConfig = parse_toml(DATA, toml_text)?;
+    config.validate()?;
+    Ok(config)
+}
+
+/// Parse and validate a classifier config from a TOML file on disk
+pub fn load_classifier_config_from_file(
+    path: impl AsRef<Path>,
+) -> Result<ClassifierConfig, ConfigError> {
+    load_file(path.as_ref(), load_classifier_config_from_str)
+}
+
+fn default_classifier_config() -> ClassifierConfig {
+    ClassifierConfig::for_skill_level(embedded_presets().default_skill_level)
+}
+
+fn config_lock() -> &'static RwLock<ClassifierConfig> {
+    CLASSIFIER_CONFIG.get_or_init(|| RwLock::new(default_classifier_config()))
+}
+
+/// Classifier config used when a shot does not specify its own
+pub fn classifier_config() -> ClassifierConfig {
+    *config_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Replace the classifier config used by all subsequent classifications
+pub fn set_classifier_config(config: ClassifierConfig) -> Result<(), ConfigError> {
+    config.validate()?;
+    *config_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = config;
+    Ok(())
+}
+
+/// Restore the default classifier preset
+pub fn reset_classifier_config() {
+    *config_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = default_classifier_config();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_preset_matches_original_thresholds() {
+        assert_eq!(default_classifier_config(), ClassifierConfig::default());
+    }
+
+    #[test]
+    fn test_presets_widen_for_beginners() {
+        let beginner = ClassifierConfig::for_skill_level(SkillLevel::Beginner);
+        let tour = ClassifierConfig::for_skill_level(SkillLevel::Tour);
+        assert!(beginner.straight_max_hla_degrees > tour.straight_max_hla_degrees);
+        assert!(
+            beginner.severe_curve_min_spin_axis_degrees > tour.severe_curve_min_spin_axis_degrees
+        );
+    }
+
+    #[test]
+    fn test_partial_config_uses_defaults() {
+        let config = load_classifier_config_from_str("straight_max_hla_degrees = 4.5").unwrap();
+        assert_eq!(config.straight_max_hla_degrees, 4.5);
+        assert_eq!(config.severe_curve_min_spin_axis_degrees, 12.0);
+    }
+
+    #[test]
+    fn test_rejects_misspelled_threshold() {
+        let error = load_classifier_config_from_str("straight_max_hla_degree = 4.5").unwrap_err();
+        assert!(matches!(error, ConfigError::Parse { .. }));
+        assert!(error.to_string().contains("straight_max_hla_degree"));
+    }
+
+    #[test]
+    fn test_rejects_inverted_curve_thresholds() {
+        let text = "curve_min_spin_axis_degrees = 10.0\nsevere_curve_min_spin_axis_degrees = 6.0";
+        assert!(matches!(
+            load_classifier_config_from_str(text),
+            Err(ConfigError::Invalid { .. })
+        ));
+    }
+}
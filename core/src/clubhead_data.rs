@@ -1,76 +1,14 @@
+use crate::d_plane::{calculate_d_plane_delivery, DPlaneClubDelivery};
+use crate::impact_bands::{band_for_ball_speed, ImpactBand};
+use std::f64::consts::PI;
+
 /// Physics constants for clubhead speed estimation
 const BALL_MASS: f64 = 0.04593; // kg (golf ball)
 const CLUBHEAD_MASS: f64 = 0.200; // kg (~200g, typical driver head)
 const DRIVER_COR_LIMIT: f64 = 0.83; // USGA/R&A limit for coefficient of restitution
 const MIN_EFFECTIVE_COR: f64 = 0.52; // Represents a glancing or highly inefficient strike
-
-#[derive(Clone, Copy)]
-struct ImpactBand {
-    max_ball_speed_mps: f64,
-    base_cor: f64,
-    optimal_launch_deg: f64,
-    launch_tolerance_deg: f64,
-    optimal_spin_rpm: f64,
-    spin_tolerance_rpm: f64,
-    face_influence_ratio: f64,
-    spin_axis_gain: f64,
-}
-
-const IMPACT_BANDS: [ImpactBand; 4] = [
-    // Wedges and short chips
-    ImpactBand {
-        max_ball_speed_mps: 40.0, // ~90 mph
-        base_cor: 0.55,
-        optimal_launch_deg: 28.0,
-        launch_tolerance_deg: 15.0,
-        optimal_spin_rpm: 9000.0,
-        spin_tolerance_rpm: 4000.0,
-        face_influence_ratio: 0.65,
-        spin_axis_gain: 1.7,
-    },
-    // Short and mid irons
-    ImpactBand {
-        max_ball_speed_mps: 50.0, // ~112 mph
-        base_cor: 0.66,
-        optimal_launch_deg: 20.0,
-        launch_tolerance_deg: 12.0,
-        optimal_spin_rpm: 7000.0,
-        spin_tolerance_rpm: 2500.0,
-        face_influence_ratio: 0.72,
-        spin_axis_gain: 2.1,
-    },
-    // Long irons and hybrids
-    ImpactBand {
-        max_ball_speed_mps: 60.0, // ~134 mph
-        base_cor: 0.72,
-        optimal_launch_deg: 16.0,
-        launch_tolerance_deg: 10.0,
-        optimal_spin_rpm: 5000.0,
-        spin_tolerance_rpm: 2000.0,
-        face_influence_ratio: 0.78,
-        spin_axis_gain: 2.4,
-    },
-    // Fairway woods and drivers
-    ImpactBand {
-        max_ball_speed_mps: f64::INFINITY,
-        base_cor: DRIVER_COR_LIMIT,
-        optimal_launch_deg: 12.0,
-        launch_tolerance_deg: 8.0,
-        optimal_spin_rpm: 2500.0,
-        spin_tolerance_rpm: 1500.0,
-        face_influence_ratio: 0.85,
-        spin_axis_gain: 2.8,
-    },
-];
-
-fn band_for_ball_speed(ball_speed_mps: f64) -> ImpactBand {
-    for band in IMPACT_BANDS {
-        if ball_speed_mps <= band.max_ball_speed_mps {
-            return band;
-        }
-    }
-    IMPACT_BANDS[IMPACT_BANDS.len() - 1]
-}
+const MAX_SPIN_LOFT_DEG: f64 = 60.0; // Beyond this the ball slides up a flop-shot face
+const CLUB_LOFT_WEIGHT: f64 = 0.5; // Trust placed in static loft vs. the spin loft estimate
 
 /// Estimated face/path relationship for a given shot
 pub struct ClubFacePathEstimates {
@@ -98,6 +36,117 @@ pub fn estimate_club_face_path(
     }
 }
 
+/// Invert spin = gain * ball_speed * sin(spin_loft), keeping the ratio in asin's domain.
+pub(crate) fn spin_loft_from_spin(
+    band: &ImpactBand,
+    ball_speed_mps: f64,
+    total_spin_rpm: f64,
+) -> f64 {
+    let spin_ratio = (total_spin_rpm.max(0.0) / (band.spin_loft_gain * ball_speed_mps)).min(1.0);
+    (spin_ratio.asin() * 180.0 / PI).min(MAX_SPIN_LOFT_DEG)
+}
+
+/// Estimated vertical delivery (attack angle and loft) for a given shot
+pub struct ClubLoftAttackEstimates {
+    pub attack_angle_degrees: f64,
+    pub dynamic_loft_degrees: f64,
+    pub spin_loft_degrees: f64,
+}
+
+/// Estimate attack angle, dynamic loft and spin loft using a vertical D-plane model.
+///
+/// Spin loft (dynamic loft minus attack angle) is recovered from the spin rate,
+/// which scales with ball speed and the sine of spin loft. Launch angle then sits
+/// a band-specific fraction of the way from the attack angle toward the dynamic loft:
+/// `launch = attack + launch_loft_ratio * spin_loft`.
+///
+/// When the static club loft is known it acts as a prior on dynamic loft, since delivered
+/// loft rarely strays far from the loft stamped on the club. Attack angle, dynamic loft and
+/// spin loft are solved together so the launch relation still holds.
+///
+/// # Arguments
+/// * `ball_speed_mps` - Ball speed in meters per second
+/// * `vertical_launch_angle_deg` - Vertical launch angle in degrees
+/// * `total_spin_rpm` - Total spin rate in RPM
+/// * `club_loft_degrees` - Static loft of the club in degrees, if known
+///
+/// # Returns
+/// Attack angle (positive = hitting up), dynamic loft and spin loft in degrees
+pub fn estimate_club_loft_attack(
+    ball_speed_mps: f64,
+    vertical_launch_angle_deg: f64,
+    total_spin_rpm: f64,
+    club_loft_degrees: Option<f64>,
+) -> ClubLoftAttackEstimates {
+    let ball_speed = ball_speed_mps.max(5.0);
+    let band = band_for_ball_speed(ball_speed);
+    let spin_loft = spin_loft_from_spin(&band, ball_speed, total_spin_rpm);
+
+    let launch = vertical_launch_angle_deg;
+    let ratio = band.launch_loft_ratio;
+    let model_dynamic_loft = launch + (1.0 - ratio) * spin_loft;
+
+    // Along the launch relation, dynamic loft = launch + (1 - ratio) * spin loft, so moving
+    // dynamic loft by x moves spin loft by x / (1 - ratio). Weighing that spin loft change
+    // against the distance from the static loft keeps the spin estimate dominant.
+    let dynamic_loft = match club_loft_degrees {
+        Some(loft) => {
+            let loft_weight = CLUB_LOFT_WEIGHT * (1.0 - ratio).powi(2);
+            let spin_weight = 1.0 - CLUB_LOFT_WEIGHT;
+            (spin_weight * model_dynamic_loft + loft_weight * loft) / (spin_weight + loft_weight)
+        }
+        None => model_dynamic_loft,
+    };
+    let spin_loft = if ratio < 1.0 {
+        (dynamic_loft - launch) / (1.0 - ratio)
+    } else {
+        spin_loft
+    };
+    let attack_angle = dynamic_loft - spin_loft;
+
+    ClubLoftAttackEstimates {
+        attack_angle_degrees: attack_angle,
+        dynamic_loft_degrees: dynamic_loft,
+        spin_loft_degrees: spin_loft,
+    }
+}
+
+/// Estimate full club delivery (face, path, attack angle and loft) with a 3D D-plane.
+///
+/// Unlike [`estimate_club_face_path`], which treats the horizontal plane on its own,
+/// this solves face and path together with the vertical plane: spin loft comes from
+/// the spin rate, the spin axis orients the D-plane around the launch direction, and
+/// the band's `launch_loft_ratio` sets how far the ball starts from path toward face.
+///
+/// # Arguments
+/// * `ball_speed_mps` - Ball speed in meters per second
+/// * `horizontal_launch_angle_deg` - Horizontal launch angle in degrees
+/// * `vertical_launch_angle_deg` - Vertical launch angle in degrees
+/// * `total_spin_rpm` - Total spin rate in RPM
+/// * `spin_axis_degrees` - Spin axis in degrees (positive = fade/slice)
+///
+/// # Returns
+/// Club path, face angle, face-to-path, attack angle and dynamic loft in degrees
+pub fn estimate_d_plane_delivery(
+    ball_speed_mps: f64,
+    horizontal_launch_angle_deg: f64,
+    vertical_launch_angle_deg: f64,
+    total_spin_rpm: f64,
+    spin_axis_degrees: f64,
+) -> DPlaneClubDelivery {
+    let ball_speed = ball_speed_mps.max(5.0);
+    let band = band_for_ball_speed(ball_speed);
+    let spin_loft = spin_loft_from_spin(&band, ball_speed, total_spin_rpm);
+
+    calculate_d_plane_delivery(
+        horizontal_launch_angle_deg,
+        vertical_launch_angle_deg,
+        spin_axis_degrees,
+        spin_loft,
+        band.launch_loft_ratio,
+    )
+}
+
 /// Calculate smash factor (ball speed / clubhead speed ratio)
 ///
 /// Smash factor is a measure of energy transfer efficiency from club to ball.
@@ -138,6 +187,19 @@ pub fn estimate_clubhead_speed(
     ball_speed_mps: f64,
     vertical_launch_angle_deg: f64,
     total_spin_rpm: f64,
+) -> f64 {
+    ball_speed_mps
+        / expected_smash_factor(ball_speed_mps, vertical_launch_angle_deg, total_spin_rpm)
+}
+
+/// Smash factor expected for a centered strike with the given launch conditions.
+///
+/// Shared by the clubhead speed estimate and the forward impact model so the two stay
+/// exact inverses of each other.
+pub(crate) fn expected_smash_factor(
+    ball_speed_mps: f64,
+    vertical_launch_angle_deg: f64,
+    total_spin_rpm: f64,
 ) -> f64 {
     // Clamp user inputs to reasonable on-course ranges to avoid runaway penalties.
     let launch_angle = vertical_launch_angle_deg.clamp(-5.0, 70.0);
@@ -170,9 +232,7 @@ pub fn estimate_clubhead_speed(
 
     // Convert the effective COR into a smash factor via a simple collision model.
     let mass_ratio = BALL_MASS / CLUBHEAD_MASS;
-    let smash_factor = (1.0 + effective_cor) / (1.0 + mass_ratio);
-
-    ball_speed_mps / smash_factor
+    (1.0 + effective_cor) / (1.0 + mass_ratio)
 }
 
 #[cfg(test)]
@@ -290,6 +350,110 @@ mod tests {
         assert!(estimates.club_path_degrees > 2.0);
     }
 
+    #[test]
+    fn test_loft_attack_estimation_driver() {
+        // Driver: 160 mph ball speed, 12° launch, 2500 rpm → slight upward strike, ~14° loft
+        let estimates = estimate_club_loft_attack(71.5, 12.0, 2500.0, None);
+        assert!(
+            estimates.spin_loft_degrees > 10.0 && estimates.spin_loft_degrees < 16.0,
+            "Spin loft {} should be typical for driver",
+            estimates.spin_loft_degrees
+        );
+        assert!(
+            estimates.attack_angle_degrees > -2.0 && estimates.attack_angle_degrees < 4.0,
+            "Attack angle {} should be near level for driver",
+            estimates.attack_angle_degrees
+        );
+        assert!(
+            (estimates.dynamic_loft_degrees
+                - estimates.attack_angle_degrees
+                - estimates.spin_loft_degrees)
+                .abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn test_loft_attack_estimation_iron() {
+        // 7-iron: 120 mph ball speed, 16° launch, 7000 rpm → descending blow
+        let estimates = estimate_club_loft_attack(53.6, 16.0, 7000.0, None);
+        assert!(
+            estimates.attack_angle_degrees < -1.0 && estimates.attack_angle_degrees > -8.0,
+            "Attack angle {} should be descending for iron",
+            estimates.attack_angle_degrees
+        );
+        assert!(
+            estimates.dynamic_loft_degrees > 16.0 && estimates.dynamic_loft_degrees < 26.0,
+            "Dynamic loft {} should be reasonable for 7-iron",
+            estimates.dynamic_loft_degrees
+        );
+    }
+
+    #[test]
+    fn test_loft_attack_estimation_uses_club_loft() {
+        let without_loft = estimate_club_loft_attack(53.6, 16.0, 7000.0, None);
+        let with_loft = estimate_club_loft_attack(53.6, 16.0, 7000.0, Some(34.0));
+        assert!(with_loft.dynamic_loft_degrees > without_loft.dynamic_loft_degrees);
+        assert!(
+            (with_loft.spin_loft_degrees - without_loft.spin_loft_degrees).abs() < 3.0,
+            "Spin loft comes mostly from spin and should barely depend on club loft"
+        );
+    }
+
+    #[test]
+    fn test_club_loft_keeps_launch_relation() {
+        // Tour 7-iron row from benchmarks.toml
+        let band = band_for_ball_speed(53.6);
+        let without_loft = estimate_club_loft_attack(53.6, 16.3, 7100.0, None);
+        let with_loft = estimate_club_loft_attack(53.6, 16.3, 7100.0, Some(34.0));
+        for estimates in [&without_loft, &with_loft] {
+            let launch = estimates.attack_angle_degrees
+                + band.launch_loft_ratio * estimates.spin_loft_degrees;
+            assert!(
+                (launch - 16.3).abs() < 1e-9,
+                "Launch relation broken: {}",
+                launch
+            );
+            assert!(
+                (estimates.dynamic_loft_degrees
+                    - estimates.attack_angle_degrees
+                    - estimates.spin_loft_degrees)
+                    .abs()
+                    < 1e-9
+            );
+        }
+        assert!(
+            with_loft.attack_angle_degrees < 0.0,
+            "A tour 7-iron hits down, got {}",
+            with_loft.attack_angle_degrees
+        );
+        assert!((with_loft.attack_angle_degrees - without_loft.attack_angle_degrees).abs() < 3.0);
+    }
+
+    #[test]
+    fn test_d_plane_delivery_driver_cut() {
+        // Same shot as the linear cut test: starts left, curves right
+        let delivery = estimate_d_plane_delivery(70.0, -2.0, 12.0, 2800.0, 15.0);
+        assert!(
+            delivery.club_face_to_path_degrees > 1.0 && delivery.club_face_to_path_degrees < 8.0,
+            "Face-to-path {} should be open for a cut",
+            delivery.club_face_to_path_degrees
+        );
+        assert!(delivery.club_path_degrees < -2.0);
+        assert!(delivery.club_face_to_target_degrees > delivery.club_path_degrees);
+    }
+
+    #[test]
+    fn test_d_plane_delivery_matches_vertical_model() {
+        // With no curvature the 3D solution collapses onto the vertical estimator
+        let delivery = estimate_d_plane_delivery(53.6, 0.0, 16.0, 7000.0, 0.0);
+        let vertical = estimate_club_loft_attack(53.6, 16.0, 7000.0, None);
+        assert!(delivery.club_path_degrees.abs() < 1e-9);
+        assert!(delivery.club_face_to_target_degrees.abs() < 1e-9);
+        assert!((delivery.attack_angle_degrees - vertical.attack_angle_degrees).abs() < 1e-6);
+        assert!((delivery.dynamic_loft_degrees - vertical.dynamic_loft_degrees).abs() < 1e-6);
+    }
+
     #[test]
     fn test_smash_factor_driver() {
         // Typical driver: 160 mph ball / 107 mph club = 1.50 smash factor
@@ -0,0 +1,413 @@
+use crate::benchmarks::{club_benchmark_for_speed, normalize_club};
+use crate::clubhead_data::expected_smash_factor;
+use crate::impact_bands::band_for_ball_speed;
+use crate::localization::describe_recommendation;
+use crate::DerivedValues;
+use serde::{Deserialize, Serialize};
+
+/// Recommendation thresholds
+const FACE_TO_PATH_LIMIT_DEG: f64 = 3.0; // A controlled fade/draw stays inside this
+const FACE_TO_PATH_SCALE_DEG: f64 = 2.0;
+const DRIVER_ATTACK_WINDOW_DEG: (f64, f64) = (-1.0, 5.0);
+const WOOD_ATTACK_WINDOW_DEG: (f64, f64) = (-4.0, 2.0);
+const IRON_ATTACK_WINDOW_DEG: (f64, f64) = (-6.0, 0.0);
+const ATTACK_SCALE_DEG: f64 = 2.0;
+const CLUB_SPIN_WINDOW_SD: f64 = 1.5; // Spin within this many benchmark SDs is fine
+const MIN_FULL_SWING_BALL_SPEED: f64 = 15.0; // Putts and chips are not coached
+const SMASH_DEFICIT_LIMIT: f64 = 0.05;
+const SMASH_SCALE: f64 = 0.05;
+
+/// Swing change suggested by [`coaching_recommendations`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendationId {
+    OpenFaceToPath,
+    ClosedFaceToPath,
+    HitUpWithDriver,
+    HitDownOnIrons,
+    SteepAttackAngle,
+    ReduceSpin,
+    AddSpin,
+    CenterContact,
+}
+
+impl RecommendationId {
+    pub const ALL: [RecommendationId; 8] = [
+        RecommendationId::OpenFaceToPath,
+        RecommendationId::ClosedFaceToPath,
+        RecommendationId::HitUpWithDriver,
+        RecommendationId::HitDownOnIrons,
+        RecommendationId::SteepAttackAngle,
+        RecommendationId::ReduceSpin,
+        RecommendationId::AddSpin,
+        RecommendationId::CenterContact,
+    ];
+
+    /// Key used in `locales/recommendations/recommendations_<locale>.toml`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RecommendationId::OpenFaceToPath => "open_face_to_path",
+            RecommendationId::ClosedFaceToPath => "closed_face_to_path",
+            RecommendationId::HitUpWithDriver => "hit_up_with_driver",
+            RecommendationId::HitDownOnIrons => "hit_down_on_irons",
+            RecommendationId::SteepAttackAngle => "steep_attack_angle",
+            RecommendationId::ReduceSpin => "reduce_spin",
+            RecommendationId::AddSpin => "add_spin",
+            RecommendationId::CenterContact => "center_contact",
+        }
+    }
+}
+
+/// A prioritized, localized suggestion and the number that triggered it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recommendation {
+    pub id: RecommendationId,
+
+    /// 1 = work on this first
+    pub priority: usize,
+
+    /// Output field that triggered the recommendation, e.g. `club_face_to_path_degrees`
+    pub metric: String,
+    pub value: f64,
+
+    /// Window the metric should be brought into; an open side is not coached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_min: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_max: Option<f64>,
+
+    pub title: String,
+    pub advice: String,
+}
+
+/// A rule that fired, before ranking and localization
+struct Finding {
+    id: RecommendationId,
+    metric: &'static str,
+    value: f64,
+    target_min: Option<f64>,
+    target_max: Option<f64>,
+    /// Distance outside the window in rule-specific units; ranks the findings
+    severity: f64,
+}
+
+/// Check a value against a window, firing `below` or `above` when it falls outside
+fn check_window(
+    metric: &'static str,
+    value: f64,
+    (min, max): (Option<f64>, Option<f64>),
+    scale: f64,
+    below: Option<RecommendationId>,
+    above: Option<RecommendationId>,
+) -> Option<Finding> {
+    let (id, excess) = match (min, max) {
+        (Some(min), _) if value < min => (below?, min - value),
+        (_, Some(max)) if value > max => (above?, value - max),
+        _ => return None,
+    };
+    Some(Finding {
+        id,
+        metric,
+        value,
+        target_min: min,
+        target_max: max,
+        severity: excess / scale,
+    })
+}
+
+/// Face-to-path beyond a controlled curve: positive (open) slices, negative (closed) hooks
+fn face_to_path_rule(shot: &DerivedValues) -> Option<Finding> {
+    check_window(
+        "club_face_to_path_degrees",
+        shot.club_face_to_path_degrees?,
+        (Some(-FACE_TO_PATH_LIMIT_DEG), Some(FACE_TO_PATH_LIMIT_DEG)),
+        FACE_TO_PATH_SCALE_DEG,
+        Some(RecommendationId::ClosedFaceToPath),
+        Some(RecommendationId::OpenFaceToPath),
+    )
+}
+
+/// Attack angle judged by club type: drivers want to hit up, irons down, woods level
+fn attack_angle_rule(shot: &DerivedValues) -> Option<Finding> {
+    let attack = shot.attack_angle_degrees?;
+    let club = shot.club.as_deref().map(normalize_club);
+    let is_driver = match club.as_deref() {
+        Some(club) => club == "driver" || club == "1w",
+        // Without a club label, fall back to the fastest impact band
+        None => shot
+            .ball_speed_meters_per_second
+            .is_some_and(|speed| band_for_ball_speed(speed).max_ball_speed_mps.is_infinite()),
+    };
+    // "3w", "5h"; wedges ("pw", "sw", ...) play in the iron window
+    let is_wood = club.as_deref().is_some_and(|club| {
+        club.strip_suffix(['w', 'h'])
+            .is_some_and(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+    });
+
+    let (window, below, above) = if is_driver {
+        (
+            DRIVER_ATTACK_WINDOW_DEG,
+            RecommendationId::HitUpWithDriver,
+            None,
+        )
+    } else if is_wood {
+        (
+            WOOD_ATTACK_WINDOW_DEG,
+            RecommendationId::SteepAttackAngle,
+            None,
+        )
+    } else {
+        (
+            IRON_ATTACK_WINDOW_DEG,
+            RecommendationId::SteepAttackAngle,
+            Some(RecommendationId::HitDownOnIrons),
+        )
+    };
+    check_window(
+        "attack_angle_degrees",
+        attack,
+        (Some(window.0), Some(window.1)),
+        ATTACK_SCALE_DEG,
+        Some(below),
+        above,
+    )
+}
+
+/// Total spin against the club's benchmark (the band nearest the shot's ball speed), or
+/// against one tolerance of the impact band's optimum when the club is unknown
+fn spin_rule(shot: &DerivedValues) -> Option<Finding> {
+    let ball_speed = shot.ball_speed_meters_per_second?;
+    let club_benchmark = shot
+        .club
+        .as_deref()
+        .and_then(|club| club_benchmark_for_speed(club, ball_speed));
+    let (optimum, tolerance) = match club_benchmark {
+        Some(row) => (row.spin_rpm, CLUB_SPIN_WINDOW_SD * row.spin_sd_rpm),
+        None => {
+            let band = band_for_ball_speed(ball_speed);
+            (band.optimal_spin_rpm, band.spin_tolerance_rpm)
+        }
+    };
+    check_window(
+        "total_spin_rpm",
+        shot.total_spin_rpm?,
+        (Some(optimum - tolerance), Some(optimum + tolerance)),
+        tolerance,
+        Some(RecommendationId::AddSpin),
+        Some(RecommendationId::ReduceSpin),
+    )
+}
+
+/// Smash factor short of what a centered strike with this launch would give. Only called with
+/// a measured club speed: an estimated one reproduces the expected smash.
+fn smash_rule(shot: &DerivedValues) -> Option<Finding> {
+    let expected = expected_smash_factor(
+        shot.ball_speed_meters_per_second?,
+        shot.vertical_launch_angle_degrees?,
+        shot.total_spin_rpm?,
+    );
+    check_window(
+        "smash_factor",
+        shot.smash_factor?,
+        (Some(expected - SMASH_DEFICIT_LIMIT), None),
+        SMASH_SCALE,
+        Some(RecommendationId::CenterContact),
+        None,
+    )
+}
+
+/// Decimal places used when a value is written into recommendation text
+fn precision(metric: &str) -> usize {
+    match metric {
+        "smash_factor" => 2,
+        "total_spin_rpm" => 0,
+        _ => 1,
+    }
+}
+
+/// Prioritized recommendations for one shot, with text in `locale` (falling back to English).
+///
+/// Rules look at face-to-path, attack angle (by club type), total spin against the club's
+/// benchmark (or the impact band's optimum when the club has none) and smash factor deficit;
+/// club delivery is golfer-relative. The smash rule only runs when `club_speed_measured`, since
+/// an estimated club speed is derived from the expected smash. Returns `None` for putts and chips, or when no rule has the values it needs.
+pub fn coaching_recommendations(
+    shot: &DerivedValues,
+    locale: &str,
+    club_speed_measured: bool,
+) -> Option<Vec<Recommendation>> {
+    if shot
+        .ball_speed_meters_per_second
+        .is_some_and(|speed| speed < MIN_FULL_SWING_BALL_SPEED)
+    {
+        return None;
+    }
+    let has_inputs = shot.club_face_to_path_degrees.is_some()
+        || shot.attack_angle_degrees.is_some()
+        || shot.ball_speed_meters_per_second.is_some();
+    if !has_inputs {
+        return None;
+    }
+
+    let rules: &[fn(&DerivedValues) -> Option<Finding>] = if club_speed_measured {
+        &[face_to_path_rule, attack_angle_rule, spin_rule, smash_rule]
+    } else {
+        &[face_to_path_rule, attack_angle_rule, spin_rule]
+    };
+    let mut findings: Vec<Finding> = rules
+        .iter()
+        .filter_map(|rule| rule(shot))
+        .filter(|finding| finding.value.is_finite())
+        .collect();
+    // Stable sort keeps rule order for equally severe findings
+    findings.sort_by(|a, b| b.severity.total_cmp(&a.severity));
+
+    Some(
+        findings
+            .into_iter()
+            .enumerate()
+            .map(|(i, finding)| {
+                let text = describe_recommendation(finding.id, locale);
+                let value = format!("{:.*}", precision(finding.metric), finding.value.abs());
+                Recommendation {
+                    id: finding.id,
+                    priority: i + 1,
+                    metric: finding.metric.to_string(),
+                    value: finding.value,
+                    target_min: finding.target_min,
+                    target_max: finding.target_max,
+                    title: text.title.replace("{value}", &value),
+                    advice: text.advice.replace("{value}", &value),
+                }
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iron_shot() -> DerivedValues {
+        let mut shot = DerivedValues::new();
+        shot.club = Some("7 Iron".to_string());
+        shot.ball_speed_meters_per_second = Some(50.0);
+        shot.vertical_launch_angle_degrees = Some(18.0);
+        shot.total_spin_rpm = Some(6500.0);
+        shot.club_face_to_path_degrees = Some(1.0);
+        shot.attack_angle_degrees = Some(-3.0);
+        shot.smash_factor = Some(expected_smash_factor(50.0, 18.0, 6500.0));
+        shot
+    }
+
+    #[test]
+    fn test_good_shot_has_no_recommendations() {
+        assert_eq!(
+            coaching_recommendations(&iron_shot(), "en", true),
+            Some(vec![])
+        );
+        assert_eq!(
+            coaching_recommendations(&DerivedValues::new(), "en", true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_recommendations_ranked_by_severity() {
+        let mut shot = iron_shot();
+        shot.club_face_to_path_degrees = Some(5.0); // 1 unit past the window
+        shot.attack_angle_degrees = Some(2.0); // 1 unit
+        shot.smash_factor = shot.smash_factor.map(|smash| smash - 0.2); // 3 units
+
+        let recs = coaching_recommendations(&shot, "en", true).unwrap();
+        let ids: Vec<_> = recs.iter().map(|r| (r.priority, r.id)).collect();
+        assert_eq!(
+            ids,
+            [
+                (1, RecommendationId::CenterContact),
+                (2, RecommendationId::OpenFaceToPath),
+                (3, RecommendationId::HitDownOnIrons),
+            ]
+        );
+        assert_eq!(recs[1].metric, "club_face_to_path_degrees");
+        assert_eq!(
+            (recs[1].target_min, recs[1].target_max),
+            (Some(-3.0), Some(3.0))
+        );
+        assert!(recs[1].title.contains("5.0"), "{}", recs[1].title);
+        assert!(!recs[0].advice.is_empty());
+
+        // Without a measured club speed the smash factor says nothing about contact
+        let recs = coaching_recommendations(&shot, "en", false).unwrap();
+        assert_eq!(recs[0].id, RecommendationId::OpenFaceToPath);
+        assert!(recs.iter().all(|r| r.id != RecommendationId::CenterContact));
+    }
+
+    #[test]
+    fn test_attack_angle_depends_on_club() {
+        let mut driver = iron_shot();
+        driver.club = Some("Driver".to_string());
+        driver.ball_speed_meters_per_second = Some(60.0);
+        driver.vertical_launch_angle_degrees = Some(12.0);
+        driver.total_spin_rpm = Some(2800.0);
+        driver.smash_factor = None;
+        driver.attack_angle_degrees = Some(-3.0);
+        let recs = coaching_recommendations(&driver, "en", true).unwrap();
+        assert_eq!(recs[0].id, RecommendationId::HitUpWithDriver);
+
+        // Hitting up with a fairway wood is fine
+        driver.club = Some("3 wood".to_string());
+        driver.attack_angle_degrees = Some(3.0);
+        assert_eq!(coaching_recommendations(&driver, "en", true), Some(vec![]));
+    }
+
+    #[test]
+    fn test_wedges_use_iron_attack_window() {
+        let attack_ids = |club: &str, attack: f64| {
+            let mut shot = iron_shot();
+            shot.club = Some(club.to_string());
+            shot.attack_angle_degrees = Some(attack);
+            coaching_recommendations(&shot, "en", true)
+                .unwrap()
+                .into_iter()
+                .filter(|r| r.metric == "attack_angle_degrees")
+                .map(|r| r.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(attack_ids("PW", -5.0), []);
+        assert_eq!(attack_ids("Sand Wedge", -5.0), []);
+        assert_eq!(attack_ids("SW", 2.0), [RecommendationId::HitDownOnIrons]);
+        assert_eq!(
+            attack_ids("4 Hybrid", -5.0),
+            [RecommendationId::SteepAttackAngle]
+        );
+    }
+
+    #[test]
+    fn test_spin_against_band_optimum() {
+        let mut shot = iron_shot();
+        shot.club = None;
+        shot.total_spin_rpm = Some(11_000.0);
+        shot.smash_factor = None;
+        let recs = coaching_recommendations(&shot, "en", true).unwrap();
+        assert_eq!(recs[0].id, RecommendationId::ReduceSpin);
+        assert_eq!(recs[0].target_max, Some(9500.0));
+    }
+
+    #[test]
+    fn test_spin_against_club_benchmark() {
+        // Tour 7-iron row from benchmarks.toml: 53.6 m/s, 16.3°, 7100 rpm
+        let mut shot = iron_shot();
+        shot.ball_speed_meters_per_second = Some(53.6);
+        shot.vertical_launch_angle_degrees = Some(16.3);
+        shot.total_spin_rpm = Some(7100.0);
+        shot.smash_factor = None;
+        assert_eq!(coaching_recommendations(&shot, "en", true), Some(vec![]));
+
+        shot.total_spin_rpm = Some(9000.0);
+        let recs = coaching_recommendations(&shot, "en", true).unwrap();
+        assert_eq!(recs[0].id, RecommendationId::ReduceSpin);
+        assert_eq!(recs[0].target_max, Some(7100.0 + 1.5 * 570.0));
+    }
+}
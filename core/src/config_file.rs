@@ -0,0 +1,144 @@
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Error raised when data such as impact bands, a ball model or a locale cannot be loaded,
+/// saved or fails validation
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file could not be read or written
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// Not TOML, or not laid out as expected
+    Parse {
+        data: &'static str,
+        path: Option<PathBuf>,
+        message: String,
+    },
+
+    /// Well-formed, but the values cannot be used
+    Invalid {
+        data: &'static str,
+        path: Option<PathBuf>,
+        message: String,
+    },
+}
+
+impl ConfigError {
+    pub(crate) fn invalid(data: &'static str, message: impl Into<String>) -> Self {
+        ConfigError::Invalid {
+            data,
+            path: None,
+            message: message.into(),
+        }
+    }
+
+    /// Say which part of the data a parse or validation error is about, e.g. "scheme 'club'"
+    pub(crate) fn within(mut self, part: &str) -> Self {
+        if let ConfigError::Parse { message, .. } | ConfigError::Invalid { message, .. } = &mut self
+        {
+            *message = format!("{}: {}", part, message);
+        }
+        self
+    }
+
+    /// Name the file a parse or validation error came from, unless it already has one
+    fn in_file(mut self, file: &Path) -> Self {
+        if let ConfigError::Parse { path, .. } | ConfigError::Invalid { path, .. } = &mut self {
+            path.get_or_insert_with(|| file.to_path_buf());
+        }
+        self
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (what, data, path, message) = match self {
+            ConfigError::Io { path, source } => {
+                return write!(f, "Failed to access {}: {}", path.display(), source)
+            }
+            ConfigError::Parse {
+                data,
+                path,
+                message,
+            } => ("Failed to parse", data, path, message),
+            ConfigError::Invalid {
+                data,
+                path,
+                message,
+            } => ("Invalid", data, path, message),
+        };
+        match path {
+            Some(path) => write!(f, "{} {} in {}: {}", what, data, path.display(), message),
+            None => write!(f, "{} {}: {}", what, data, message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Deserialize `data` from TOML text; validation is up to the caller
+pub(crate) fn parse_toml<T: DeserializeOwned>(
+    data: &'static str,
+    toml_text: &str,
+) -> Result<T, ConfigError> {
+    toml::from_str(toml_text).map_err(|e| ConfigError::Parse {
+        data,
+        path: None,
+        message: e.to_string(),
+    })
+}
+
+/// Read a file and hand its text to `load`, naming the file in any error
+pub(crate) fn load_file<T>(
+    path: &Path,
+    load: impl FnOnce(&str) -> Result<T, ConfigError>,
+) -> Result<T, ConfigError> {
+    let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    load(&text).map_err(|e| e.in_file(path))
+}
+
+/// Write `text` to a file, naming the file in any error
+pub(crate) fn save_file(path: &Path, text: &str) -> Result<(), ConfigError> {
+    std::fs::write(path, text).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_name_the_file() {
+        let missing = Path::new("/nonexistent/impact_bands.toml");
+        let error = load_file(missing, |_| Ok(())).unwrap_err();
+        assert!(matches!(error, ConfigError::Io { .. }));
+        assert!(error.to_string().contains("/nonexistent/impact_bands.toml"));
+
+        let error = ConfigError::invalid("impact bands", "band 0: too fast").in_file(missing);
+        assert_eq!(
+            error.to_string(),
+            "Invalid impact bands in /nonexistent/impact_bands.toml: band 0: too fast"
+        );
+
+        let error = parse_toml::<toml::Table>("ball model", "drag_scale = ").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Failed to parse ball model: "));
+    }
+}
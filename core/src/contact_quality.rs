@@ -0,0 +1,161 @@
+use crate::clubhead_data::expected_smash_factor;
+use crate::impact::ImpactLocationEstimate;
+use crate::impact_bands::band_for_ball_speed;
+use serde::{Deserialize, Serialize};
+
+/// Contact quality detection constants
+const MIN_FULL_SWING_BALL_SPEED: f64 = 15.0; // Slower shots are putts/chips, not graded
+const TYPICAL_SMASH: f64 = 1.4; // Picks the intended club from a measured club speed
+const TOPPED_LAUNCH_FRACTION: f64 = 0.3; // Launch below 30% of the band optimum
+const TOPPED_SPIN_FRACTION: f64 = 0.5;
+const SKIED_MIN_LAUNCH_UNITS: f64 = 2.0; // Launch tolerances above optimal
+const SKIED_MIN_SPIN_UNITS: f64 = 0.5;
+const THIN_MAX_LAUNCH_UNITS: f64 = -1.0;
+const THIN_MAX_SPIN_UNITS: f64 = -0.5;
+const FAT_MIN_SMASH_DEFICIT: f64 = 0.08;
+const FAT_MIN_LAUNCH_UNITS: f64 = -0.5;
+const FAT_MAX_SPIN_UNITS: f64 = 0.5;
+const TOE_HEEL_MIN_OFFSET_MM: f64 = 10.0;
+const TOE_HEEL_MIN_CONFIDENCE: f64 = 0.5;
+
+/// How the ball was struck
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactQuality {
+    Solid,
+    Thin,
+    Fat,
+    Topped,
+    Skied,
+    Toe,
+    Heel,
+}
+
+/// Detect common mishits from ball launch against the impact band's optimal window.
+///
+/// Launch and spin deviations are measured in band tolerances. Fat strikes need a measured
+/// club speed (to see the smash factor deficit), and toe/heel strikes need a strike location
+/// that is either measured or confidently inferred.
+///
+/// # Arguments
+/// * `ball_speed_mps` - Ball speed in m/s
+/// * `measured_club_speed_mps` - Club speed from the launch monitor, if any
+/// * `vertical_launch_angle_deg` - Vertical launch angle
+/// * `total_spin_rpm` - Total spin
+/// * `impact_location` - Strike location on the face, if known
+///
+/// # Returns
+/// The detected contact quality, or `None` for putts and very slow shots
+pub fn detect_contact_quality(
+    ball_speed_mps: f64,
+    measured_club_speed_mps: Option<f64>,
+    vertical_launch_angle_deg: f64,
+    total_spin_rpm: f64,
+    impact_location: Option<&ImpactLocationEstimate>,
+) -> Option<ContactQuality> {
+    if ball_speed_mps < MIN_FULL_SWING_BALL_SPEED {
+        return None;
+    }
+
+    // A mishit loses ball speed, so judge it against the club that was swung when we know it
+    let reference_speed = measured_club_speed_mps
+        .map(|club| (club * TYPICAL_SMASH).max(ball_speed_mps))
+        .unwrap_or(ball_speed_mps);
+    let band = band_for_ball_speed(reference_speed);
+
+    let launch_units =
+        (vertical_launch_angle_deg - band.optimal_launch_deg) / band.launch_tolerance_deg;
+    let spin_units = (total_spin_rpm - band.optimal_spin_rpm) / band.spin_tolerance_rpm;
+
+    if vertical_launch_angle_deg < band.optimal_launch_deg * TOPPED_LAUNCH_FRACTION
+        && total_spin_rpm < band.optimal_spin_rpm * TOPPED_SPIN_FRACTION
+    {
+        return Some(ContactQuality::Topped);
+    }
+
+    if launch_units > SKIED_MIN_LAUNCH_UNITS && spin_units > SKIED_MIN_SPIN_UNITS {
+        return Some(ContactQuality::Skied);
+    }
+
+    if let Some(location) = impact_location {
+        if location.confidence >= TOE_HEEL_MIN_CONFIDENCE {
+            if location.horizontal_millimeters > TOE_HEEL_MIN_OFFSET_MM {
+                return Some(ContactQuality::Toe);
+            }
+            if location.horizontal_millimeters < -TOE_HEEL_MIN_OFFSET_MM {
+                return Some(ContactQuality::Heel);
+            }
+        }
+    }
+
+    if launch_units < THIN_MAX_LAUNCH_UNITS && spin_units < THIN_MAX_SPIN_UNITS {
+        return Some(ContactQuality::Thin);
+    }
+
+    if let Some(club_speed) = measured_club_speed_mps.filter(|speed| *speed > 0.0) {
+        let expected =
+            expected_smash_factor(ball_speed_mps, vertical_launch_angle_deg, total_spin_rpm);
+        let smash_deficit = 1.0 - (ball_speed_mps / club_speed) / expected;
+        if smash_deficit > FAT_MIN_SMASH_DEFICIT
+            && launch_units > FAT_MIN_LAUNCH_UNITS
+            && spin_units < FAT_MAX_SPIN_UNITS
+        {
+            return Some(ContactQuality::Fat);
+        }
+    }
+
+    Some(ContactQuality::Solid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solid_driver() {
+        let quality = detect_contact_quality(70.0, Some(48.0), 12.0, 2600.0, None);
+        assert_eq!(quality, Some(ContactQuality::Solid));
+    }
+
+    #[test]
+    fn test_topped_driver() {
+        // Ball speed collapses, so the measured club speed keeps us in the driver band
+        let quality = detect_contact_quality(40.0, Some(48.0), 2.0, 900.0, None);
+        assert_eq!(quality, Some(ContactQuality::Topped));
+    }
+
+    #[test]
+    fn test_skied_driver() {
+        let quality = detect_contact_quality(60.0, Some(48.0), 32.0, 4800.0, None);
+        assert_eq!(quality, Some(ContactQuality::Skied));
+    }
+
+    #[test]
+    fn test_thin_iron() {
+        let quality = detect_contact_quality(52.0, None, 5.0, 3400.0, None);
+        assert_eq!(quality, Some(ContactQuality::Thin));
+    }
+
+    #[test]
+    fn test_fat_iron() {
+        // 7-iron swung at ~38 m/s that only produced 40 m/s of ball speed
+        let quality = detect_contact_quality(40.0, Some(38.0), 21.0, 5500.0, None);
+        assert_eq!(quality, Some(ContactQuality::Fat));
+    }
+
+    #[test]
+    fn test_toe_strike_from_location() {
+        let location = ImpactLocationEstimate {
+            horizontal_millimeters: 15.0,
+            vertical_millimeters: 0.0,
+            confidence: 1.0,
+        };
+        let quality = detect_contact_quality(66.0, Some(48.0), 12.0, 2400.0, Some(&location));
+        assert_eq!(quality, Some(ContactQuality::Toe));
+    }
+
+    #[test]
+    fn test_putt_not_graded() {
+        assert_eq!(detect_contact_quality(5.0, None, 0.0, 0.0, None), None);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::vector::Vector3;
+use std::f64::consts::PI;
+
+/// Ball launch conditions predicted by the D-plane for a given club delivery
+#[derive(Debug, Clone, Copy)]
+pub struct DPlaneLaunch {
+    pub horizontal_launch_angle_degrees: f64,
+    pub vertical_launch_angle_degrees: f64,
+    pub spin_axis_degrees: f64,
+    pub spin_loft_degrees: f64,
+}
+
+/// Club delivery recovered from measured ball launch via the D-plane
+#[derive(Debug, Clone, Copy)]
+pub struct DPlaneClubDelivery {
+    pub club_path_degrees: f64,
+    pub club_face_to_target_degrees: f64,
+    pub club_face_to_path_degrees: f64,
+    pub attack_angle_degrees: f64,
+    pub dynamic_loft_degrees: f64,
+}
+
+/// Unit vector from a horizontal (right = positive) and vertical (up = positive) angle
+fn direction_from_angles(horizontal_deg: f64, vertical_deg: f64) -> Vector3 {
+    let h = horizontal_deg * PI / 180.0;
+    let v = vertical_deg * PI / 180.0;
+    Vector3::new(v.cos() * h.cos(), v.cos() * h.sin(), v.sin())
+}
+
+/// Horizontal and vertical angles (degrees) of a direction vector
+fn angles_from_direction(direction: &Vector3) -> (f64, f64) {
+    let horizontal = direction.y.atan2(direction.x) * 180.0 / PI;
+    let vertical = direction.z.clamp(-1.0, 1.0).asin() * 180.0 / PI;
+    (horizontal, vertical)
+}
+
+/// Orthonormal basis perpendicular to the launch direction.
+///
+/// The first vector is the pure-backspin axis (horizontal, pointing left of the launch
+/// direction); the second points "up" so that tilting the axis toward it produces
+/// fade/slice spin. Spin axis angles are measured from the first toward the second.
+fn spin_axis_basis(launch: &Vector3) -> (Vector3, Vector3) {
+    let backspin_axis = Vector3::new(launch.y, -launch.x, 0.0).normalize();
+    let tilt_axis = backspin_axis.cross(launch).normalize();
+    (backspin_axis, tilt_axis)
+}
+
+/// Rotate `from` toward `toward` (both unit vectors, perpendicular) by `angle_rad`
+fn rotate_toward(from: &Vector3, toward: &Vector3, angle_rad: f64) -> Vector3 {
+    let (s, c) = angle_rad.sin_cos();
+    Vector3::new(
+        from.x * c + toward.x * s,
+        from.y * c + toward.y * s,
+        from.z * c + toward.z * s,
+    )
+}
+
+/// Predict start direction, spin axis and spin loft from club delivery using a 3D D-plane.
+///
+/// The face normal and club path vectors span the D-plane. The ball starts between the
+/// two, `face_weight` of the way from the path toward the face normal, and spins about
+/// the normal of that plane. Spin loft is the full 3D angle between face and path.
+///
+/// # Arguments
+/// * `club_path_degrees` - Horizontal club path (positive = in-to-out / right)
+/// * `club_face_to_target_degrees` - Face angle (positive = open / right)
+/// * `attack_angle_degrees` - Vertical club path (positive = hitting up)
+/// * `dynamic_loft_degrees` - Loft delivered at impact
+/// * `face_weight` - Fraction of start direction governed by the face (0-1, ~0.85 for driver)
+///
+/// # Returns
+/// Launch angles, spin axis (positive = fade/slice) and spin loft in degrees
+pub fn calculate_d_plane_launch(
+    club_path_degrees: f64,
+    club_face_to_target_degrees: f64,
+    attack_angle_degrees: f64,
+    dynamic_loft_degrees: f64,
+    face_weight: f64,
+) -> DPlaneLaunch {
+    let face_weight = face_weight.clamp(0.0, 1.0);
+    let face = direction_from_angles(club_face_to_target_degrees, dynamic_loft_degrees);
+    let path = direction_from_angles(club_path_degrees, attack_angle_degrees);
+
+    let spin_loft_rad = face.dot(&path).clamp(-1.0, 1.0).acos();
+
+    // Spherical interpolation from path toward face so the inverse is exact.
+    let launch = if spin_loft_rad < 1e-9 {
+        face
+    } else {
+        let path_share = ((1.0 - face_weight) * spin_loft_rad).sin() / spin_loft_rad.sin();
+        let face_share = (face_weight * spin_loft_rad).sin() / spin_loft_rad.sin();
+        Vector3::new(
+            path.x * path_share + face.x * face_share,
+            path.y * path_share + face.y * face_share,
+            path.z * path_share + face.z * face_share,
+        )
+        .normalize()
+    };
+
+    // Spin axis is the D-plane normal, expressed relative to the launch direction.
+    let plane_normal = path.cross(&face).normalize();
+    let (backspin_axis, tilt_axis) = spin_axis_basis(&launch);
+    let spin_axis_rad = plane_normal
+        .dot(&tilt_axis)
+        .atan2(plane_normal.dot(&backspin_axis));
+
+    let (horizontal, vertical) = angles_from_direction(&launch);
+
+    DPlaneLaunch {
+        horizontal_launch_angle_degrees: horizontal,
+        vertical_launch_angle_degrees: vertical,
+        spin_axis_degrees: spin_axis_rad * 180.0 / PI,
+        spin_loft_degrees: spin_loft_rad * 180.0 / PI,
+    }
+}
+
+/// Recover club face and path from measured ball launch using a 3D D-plane.
+///
+/// Exact inverse of [`calculate_d_plane_launch`]: the spin axis fixes the orientation
+/// of the D-plane around the launch direction, and the spin loft is split between
+/// face and path according to `face_weight`.
+///
+/// # Arguments
+/// * `horizontal_launch_angle_degrees` - Horizontal launch angle (positive = right)
+/// * `vertical_launch_angle_degrees` - Vertical launch angle
+/// * `spin_axis_degrees` - Spin axis (positive = fade/slice)
+/// * `spin_loft_degrees` - Angle between face normal and club path
+/// * `face_weight` - Fraction of start direction governed by the face (0-1)
+///
+/// # Returns
+/// Club path, face angle, face-to-path, attack angle and dynamic loft in degrees
+pub fn calculate_d_plane_delivery(
+    horizontal_launch_angle_degrees: f64,
+    vertical_launch_angle_degrees: f64,
+    spin_axis_degrees: f64,
+    spin_loft_degrees: f64,
+    face_weight: f64,
+) -> DPlaneClubDelivery {
+    let face_weight = face_weight.clamp(0.0, 1.0);
+    let launch = direction_from_angles(
+        horizontal_launch_angle_degrees,
+        vertical_launch_angle_degrees,
+    );
+
+    let (backspin_axis, tilt_axis) = spin_axis_basis(&launch);
+    let spin_axis_rad = spin_axis_degrees * PI / 180.0;
+    let plane_normal = rotate_toward(&backspin_axis, &tilt_axis, spin_axis_rad);
+
+    // In-plane direction perpendicular to launch, pointing toward the face normal.
+    let toward_face = plane_normal.cross(&launch).normalize();
+
+    let spin_loft_rad = spin_loft_degrees.max(0.0) * PI / 180.0;
+    let face = rotate_toward(&launch, &toward_face, (1.0 - face_weight) * spin_loft_rad);
+    let path = rotate_toward(&launch, &toward_face, -face_weight * spin_loft_rad);
+
+    let (face_angle, dynamic_loft) = angles_from_direction(&face);
+    let (club_path, attack_angle) = angles_from_direction(&path);
+
+    DPlaneClubDelivery {
+        club_path_degrees: club_path,
+        club_face_to_target_degrees: face_angle,
+        club_face_to_path_degrees: face_angle - club_path,
+        attack_angle_degrees: attack_angle,
+        dynamic_loft_degrees: dynamic_loft,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_delivery_has_no_spin_axis() {
+        let launch = calculate_d_plane_launch(0.0, 0.0, -4.0, 20.0, 0.85);
+        assert!(launch.horizontal_launch_angle_degrees.abs() < 1e-9);
+        assert!(launch.spin_axis_degrees.abs() < 1e-9);
+        assert!((launch.spin_loft_degrees - 24.0).abs() < 1e-9);
+        assert!(
+            (launch.vertical_launch_angle_degrees - 16.4).abs() < 1e-6,
+            "Launch {} should sit 85% of the way from attack to loft",
+            launch.vertical_launch_angle_degrees
+        );
+    }
+
+    #[test]
+    fn test_open_face_to_path_tilts_axis_right() {
+        // Driver: path 3° left, face 1° left → face open to path, start slightly left, fade spin
+        let launch = calculate_d_plane_launch(-3.0, -1.0, 2.0, 14.0, 0.85);
+        assert!(launch.spin_axis_degrees > 3.0 && launch.spin_axis_degrees < 15.0);
+        assert!(
+            launch.horizontal_launch_angle_degrees < -1.0
+                && launch.horizontal_launch_angle_degrees > -3.0
+        );
+    }
+
+    #[test]
+    fn test_closed_face_to_path_tilts_axis_left() {
+        let launch = calculate_d_plane_launch(4.0, 1.0, -3.0, 18.0, 0.8);
+        assert!(launch.spin_axis_degrees < -3.0);
+    }
+
+    #[test]
+    fn test_delivery_round_trip() {
+        let deliveries = [
+            (-3.0, -1.0, 2.0, 14.0, 0.85),
+            (4.0, 1.0, -3.0, 18.0, 0.8),
+            (1.5, 6.0, -6.0, 42.0, 0.75),
+            (0.0, 0.0, 0.0, 10.0, 0.9),
+        ];
+
+        for (path, face, attack, loft, weight) in deliveries {
+            let launch = calculate_d_plane_launch(path, face, attack, loft, weight);
+            let delivery = calculate_d_plane_delivery(
+                launch.horizontal_launch_angle_degrees,
+                launch.vertical_launch_angle_degrees,
+                launch.spin_axis_degrees,
+                launch.spin_loft_degrees,
+                weight,
+            );
+            assert!((delivery.club_path_degrees - path).abs() < 1e-6);
+            assert!((delivery.club_face_to_target_degrees - face).abs() < 1e-6);
+            assert!((delivery.attack_angle_degrees - attack).abs() < 1e-6);
+            assert!((delivery.dynamic_loft_degrees - loft).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,474 @@
+use crate::outcome_classifier::default_target_tolerance;
+use crate::DerivedValues;
+use serde::{Deserialize, Serialize};
+
+/// Fewest landing points needed for a covariance ellipse
+const MIN_ELLIPSE_SHOTS: usize = 3;
+
+/// Fewest landing points before any shot is flagged as an outlier
+const MIN_OUTLIER_SHOTS: usize = 5;
+
+/// Passes of "fit, drop outliers, refit" before giving up on convergence
+const MAX_OUTLIER_PASSES: usize = 5;
+
+/// Settings for [`analyze_dispersion`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DispersionOptions {
+    /// Share of shots (0-1) each reported ellipse should contain
+    pub confidence_levels: Vec<f64>,
+
+    /// Distance to the target along the target line; enables short/long bias
+    pub target_distance_meters: Option<f64>,
+
+    /// Direction of the target line (positive = right of the 0° line)
+    pub target_bearing_degrees: f64,
+
+    /// Half-width of the "centered" / "pin high" window; defaults to the outcome tolerance
+    pub bias_tolerance_meters: Option<f64>,
+
+    /// Shots outside the prediction region of this confidence (fitted without them) are outliers
+    pub outlier_confidence: f64,
+}
+
+impl Default for DispersionOptions {
+    fn default() -> Self {
+        DispersionOptions {
+            confidence_levels: vec![0.68, 0.95],
+            target_distance_meters: None,
+            target_bearing_degrees: 0.0,
+            bias_tolerance_meters: None,
+            outlier_confidence: 0.99,
+        }
+    }
+}
+
+/// Landing point relative to the target line
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LandingPoint {
+    pub downrange_meters: f64,
+
+    /// Positive = right of the target line
+    pub lateral_meters: f64,
+}
+
+/// Covariance ellipse expected to contain `confidence` of the shots
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DispersionEllipse {
+    pub confidence: f64,
+    pub center: LandingPoint,
+    pub semi_major_meters: f64,
+    pub semi_minor_meters: f64,
+
+    /// Angle of the major axis from the target line (positive = rotated toward the right)
+    pub orientation_degrees: f64,
+
+    pub area_square_meters: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LateralBias {
+    Left,
+    Centered,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepthBias {
+    Short,
+    PinHigh,
+    Long,
+}
+
+/// Where the pattern is centered relative to the target
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DispersionBias {
+    /// Centroid distance from the target line (positive = right)
+    pub lateral_meters: f64,
+    pub lateral: LateralBias,
+
+    /// Centroid distance past the target (negative = short); requires a target distance
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_meters: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<DepthBias>,
+
+    /// Aim change that would center the pattern on the target line (positive = aim right)
+    pub aim_adjustment_degrees: f64,
+
+    pub tolerance_meters: f64,
+}
+
+/// Shot flagged as not belonging to the pattern
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DispersionOutlier {
+    /// Position of the shot in the analyzed slice or session
+    pub index: usize,
+    pub landing_point: LandingPoint,
+
+    /// Distance from the centroid in standard deviations along the pattern's shape
+    pub mahalanobis_distance: f64,
+}
+
+/// Landing-pattern analysis for a group of shots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DispersionAnalysis {
+    /// Shots with a landing position, outliers included
+    pub shot_count: usize,
+
+    /// Mean landing point, outliers excluded
+    pub centroid: LandingPoint,
+
+    pub downrange_standard_deviation_meters: f64,
+    pub lateral_standard_deviation_meters: f64,
+
+    /// Correlation between depth and width (positive = long misses go right)
+    pub correlation: f64,
+
+    /// One per confidence level; empty with fewer than three shots
+    pub ellipses: Vec<DispersionEllipse>,
+
+    pub bias: DispersionBias,
+    pub outliers: Vec<DispersionOutlier>,
+}
+
+/// Landing point of a shot in the target-line frame, if its landing position is known
+pub fn landing_point(shot: &DerivedValues, target_bearing_degrees: f64) -> Option<LandingPoint> {
+    let position = shot.landing_position?;
+    if !position.x.is_finite() || !position.y.is_finite() {
+        return None;
+    }
+    let (s, c) = target_bearing_degrees.to_radians().sin_cos();
+    Some(LandingPoint {
+        downrange_meters: position.x * c + position.y * s,
+        lateral_meters: position.y * c - position.x * s,
+    })
+}
+
+/// Analyze the landing pattern of a group of shots.
+///
+/// Shots without a `landing_position` are skipped. Outliers are removed iteratively, so a
+/// single wild shot does not inflate the ellipses it is judged against.
+///
+/// # Returns
+/// `None` when no shot has a landing position
+pub fn analyze_dispersion(
+    shots: &[DerivedValues],
+    options: &DispersionOptions,
+) -> Option<DispersionAnalysis> {
+    analyze_indexed(shots.iter().enumerate(), options)
+}
+
+/// Analyze shots paired with the index reported for outliers
+pub(crate) fn analyze_indexed<'a>(
+    shots: impl IntoIterator<Item = (usize, &'a DerivedValues)>,
+    options: &DispersionOptions,
+) -> Option<DispersionAnalysis> {
+    let points: Vec<(usize, LandingPoint)> = shots
+        .into_iter()
+        .filter_map(|(index, shot)| {
+            Some((index, landing_point(shot, options.target_bearing_degrees)?))
+        })
+        .collect();
+    if points.is_empty() {
+        return None;
+    }
+
+    let inliers = find_inliers(&points, options.outlier_confidence);
+    let fit = Moments::of(
+        points
+            .iter()
+            .zip(&inliers)
+            .filter(|(_, &keep)| keep)
+            .map(|((_, p), _)| p),
+    );
+
+    let outliers = points
+        .iter()
+        .zip(&inliers)
+        .filter(|(_, &keep)| !keep)
+        .map(|(&(index, landing_point), _)| DispersionOutlier {
+            index,
+            landing_point,
+            mahalanobis_distance: fit.mahalanobis_squared(&landing_point).sqrt(),
+        })
+        .collect();
+
+    let ellipses = if fit.count >= MIN_ELLIPSE_SHOTS {
+        options
+            .confidence_levels
+            .iter()
+            .filter(|level| **level > 0.0 && **level < 1.0)
+            .map(|&level| fit.ellipse(level))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Some(DispersionAnalysis {
+        shot_count: points.len(),
+        centroid: fit.mean,
+        downrange_standard_deviation_meters: fit.downrange_variance.sqrt(),
+        lateral_standard_deviation_meters: fit.lateral_variance.sqrt(),
+        correlation: fit.correlation(),
+        ellipses,
+        bias: bias(&fit.mean, options),
+        outliers,
+    })
+}
+
+/// Flag each point as part of the pattern (`true`) or an outlier.
+///
+/// Every point is judged against the current inliers other than itself, so a wild shot cannot
+/// hide by inflating the spread it is measured with; passes repeat until the set is stable.
+fn find_inliers(points: &[(usize, LandingPoint)], confidence: f64) -> Vec<bool> {
+    let mut inliers = vec![true; points.len()];
+    if points.len() < MIN_OUTLIER_SHOTS {
+        return inliers;
+    }
+
+    for _ in 0..MAX_OUTLIER_PASSES {
+        let next: Vec<bool> = points
+            .iter()
+            .enumerate()
+            .map(|(i, (_, point))| {
+                let others = points
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i && inliers[j])
+                    .map(|(_, (_, p))| p);
+                let fit = Moments::of(others);
+                fit.count < MIN_ELLIPSE_SHOTS
+                    || fit.mahalanobis_squared(point) <= prediction_limit(fit.count, confidence)
+            })
+            .collect();
+        // Keep enough shots to describe a pattern
+        if next == inliers || next.iter().filter(|&&keep| keep).count() < MIN_ELLIPSE_SHOTS {
+            break;
+        }
+        inliers = next;
+    }
+    inliers
+}
+
+/// Squared Mahalanobis distance a new shot stays within, with `confidence`, when the pattern
+/// is estimated from `sample_size` shots (Hotelling prediction region; F(2, n-2) has a closed form)
+fn prediction_limit(sample_size: usize, confidence: f64) -> f64 {
+    let n = sample_size as f64;
+    let k = n - 2.0;
+    let tail = 1.0 - confidence.clamp(0.0, 1.0 - 1e-12);
+    let f_quantile = k / 2.0 * (tail.powf(-2.0 / k) - 1.0);
+    f_quantile * 2.0 * (n - 1.0) / k * (n + 1.0) / n
+}
+
+/// Squared Mahalanobis radius containing `confidence` of a 2D normal distribution
+fn chi_square_2d(confidence: f64) -> f64 {
+    -2.0 * (1.0 - confidence.clamp(0.0, 1.0 - 1e-12)).ln()
+}
+
+fn bias(centroid: &LandingPoint, options: &DispersionOptions) -> DispersionBias {
+    let tolerance = options.bias_tolerance_meters.unwrap_or_else(|| {
+        default_target_tolerance(
+            options
+                .target_distance_meters
+                .unwrap_or(centroid.downrange_meters),
+        )
+    });
+
+    let lateral = if centroid.lateral_meters > tolerance {
+        LateralBias::Right
+    } else if centroid.lateral_meters < -tolerance {
+        LateralBias::Left
+    } else {
+        LateralBias::Centered
+    };
+    let depth_meters = options
+        .target_distance_meters
+        .map(|target| centroid.downrange_meters - target);
+    let depth = depth_meters.map(|depth| {
+        if depth > tolerance {
+            DepthBias::Long
+        } else if depth < -tolerance {
+            DepthBias::Short
+        } else {
+            DepthBias::PinHigh
+        }
+    });
+
+    DispersionBias {
+        lateral_meters: centroid.lateral_meters,
+        lateral,
+        depth_meters,
+        depth,
+        aim_adjustment_degrees: -centroid
+            .lateral_meters
+            .atan2(centroid.downrange_meters.max(f64::EPSILON))
+            .to_degrees(),
+        tolerance_meters: tolerance,
+    }
+}
+
+/// Mean and sample covariance of a set of landing points
+struct Moments {
+    count: usize,
+    mean: LandingPoint,
+    downrange_variance: f64,
+    lateral_variance: f64,
+    covariance: f64,
+}
+
+impl Moments {
+    fn of<'a>(points: impl Iterator<Item = &'a LandingPoint> + Clone) -> Self {
+        let count = points.clone().count();
+        let n = count as f64;
+        let mean = LandingPoint {
+            downrange_meters: points.clone().map(|p| p.downrange_meters).sum::<f64>() / n,
+            lateral_meters: points.clone().map(|p| p.lateral_meters).sum::<f64>() / n,
+        };
+        let dof = (n - 1.0).max(1.0);
+        let (mut dd, mut ll, mut dl) = (0.0, 0.0, 0.0);
+        for p in points {
+            let d = p.downrange_meters - mean.downrange_meters;
+            let l = p.lateral_meters - mean.lateral_meters;
+            dd += d * d;
+            ll += l * l;
+            dl += d * l;
+        }
+        Moments {
+            count,
+            mean,
+            downrange_variance: dd / dof,
+            lateral_variance: ll / dof,
+            covariance: dl / dof,
+        }
+    }
+
+    fn correlation(&self) -> f64 {
+        let scale = (self.downrange_variance * self.lateral_variance).sqrt();
+        if scale > f64::EPSILON {
+            self.covariance / scale
+        } else {
+            0.0
+        }
+    }
+
+    /// Eigenvalues (largest first) and major-axis angle from the target line in radians
+    fn principal_axes(&self) -> (f64, f64, f64) {
+        let half_sum = (self.downrange_variance + self.lateral_variance) / 2.0;
+        let half_diff = (self.downrange_variance - self.lateral_variance) / 2.0;
+        let radius = half_diff.hypot(self.covariance);
+        let angle =
+            0.5 * (2.0 * self.covariance).atan2(self.downrange_variance - self.lateral_variance);
+        (half_sum + radius, (half_sum - radius).max(0.0), angle)
+    }
+
+    fn mahalanobis_squared(&self, point: &LandingPoint) -> f64 {
+        let d = point.downrange_meters - self.mean.downrange_meters;
+        let l = point.lateral_meters - self.mean.lateral_meters;
+        let (major, minor, angle) = self.principal_axes();
+        let (s, c) = angle.sin_cos();
+        let along = d * c + l * s;
+        let across = l * c - d * s;
+        // Floor the spread so a perfectly repeatable pattern does not make every other shot infinite
+        let floor = 0.01;
+        along.powi(2) / major.max(floor) + across.powi(2) / minor.max(floor)
+    }
+
+    fn ellipse(&self, confidence: f64) -> DispersionEllipse {
+        let (major, minor, angle) = self.principal_axes();
+        let scale = chi_square_2d(confidence).sqrt();
+        let semi_major = scale * major.sqrt();
+        let semi_minor = scale * minor.sqrt();
+        DispersionEllipse {
+            confidence,
+            center: self.mean,
+            semi_major_meters: semi_major,
+            semi_minor_meters: semi_minor,
+            orientation_degrees: angle.to_degrees(),
+            area_square_meters: std::f64::consts::PI * semi_major * semi_minor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vector3;
+
+    fn landed(x: f64, y: f64) -> DerivedValues {
+        let mut shot = DerivedValues::new();
+        shot.landing_position = Some(Vector3::new(x, y, 0.0));
+        shot
+    }
+
+    #[test]
+    fn test_ellipse_follows_pattern_shape() {
+        // Long misses go right, short misses go left
+        let shots: Vec<_> = [
+            (140.0, -4.0),
+            (145.0, -1.0),
+            (150.0, 1.0),
+            (155.0, 3.0),
+            (160.0, 6.0),
+            (150.0, 0.0),
+        ]
+        .into_iter()
+        .map(|(x, y)| landed(x, y))
+        .collect();
+        let analysis = analyze_dispersion(&shots, &DispersionOptions::default()).unwrap();
+
+        assert_eq!(analysis.shot_count, 6);
+        assert!(analysis.outliers.is_empty());
+        assert!((analysis.centroid.downrange_meters - 150.0).abs() < 1e-9);
+        assert!(analysis.correlation > 0.9);
+
+        let [inner, outer] = analysis.ellipses[..] else {
+            panic!("expected two ellipses");
+        };
+        assert!(outer.semi_major_meters > inner.semi_major_meters);
+        assert!(inner.semi_major_meters > 4.0 * inner.semi_minor_meters);
+        assert!(inner.orientation_degrees > 0.0 && inner.orientation_degrees < 45.0);
+    }
+
+    #[test]
+    fn test_bias_and_outliers() {
+        let mut shots: Vec<_> = [
+            (128.0, 9.0),
+            (131.0, 10.0),
+            (129.0, 12.0),
+            (130.0, 11.0),
+            (132.0, 10.5),
+            (130.0, 9.5),
+        ]
+        .into_iter()
+        .map(|(x, y)| landed(x, y))
+        .collect();
+        shots.insert(2, DerivedValues::new());
+        shots.push(landed(95.0, -30.0));
+
+        let options = DispersionOptions {
+            target_distance_meters: Some(140.0),
+            ..Default::default()
+        };
+        let analysis = analyze_dispersion(&shots, &options).unwrap();
+
+        assert_eq!(analysis.shot_count, 7);
+        assert_eq!(analysis.outliers.len(), 1);
+        assert_eq!(analysis.outliers[0].index, 7);
+        assert!((analysis.centroid.downrange_meters - 130.0).abs() < 1e-9);
+        assert_eq!(analysis.bias.lateral, LateralBias::Right);
+        assert_eq!(analysis.bias.depth, Some(DepthBias::Short));
+        assert!(analysis.bias.aim_adjustment_degrees < 0.0);
+    }
+
+    #[test]
+    fn test_target_bearing_rotates_frame() {
+        let shot = landed(100.0, 100.0 * 10f64.to_radians().tan());
+        let point = landing_point(&shot, 10.0).unwrap();
+        assert!(point.lateral_meters.abs() < 1e-9);
+        assert!(landing_point(&DerivedValues::new(), 0.0).is_none());
+    }
+}
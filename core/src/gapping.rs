@@ -0,0 +1,386 @@
+use crate::session::{ClubSelector, MetricStats, Session, SessionMetric};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Settings for [`gapping_report`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GappingOptions {
+    /// Carry gap the suggestions aim for between adjacent clubs
+    pub target_gap_meters: f64,
+
+    /// Smaller gaps (or overlapping carry ranges) are reported as overlaps
+    pub min_gap_meters: f64,
+
+    /// Larger gaps are reported as holes
+    pub max_gap_meters: f64,
+
+    /// Carry change for one degree of loft (less loft = longer)
+    pub carry_per_loft_degree_meters: f64,
+
+    /// Largest loft change suggested for one club, as a club fitter would bend it
+    pub max_loft_change_degrees: f64,
+
+    /// Clubs with fewer shots with a carry are left out of the report
+    pub min_shots: usize,
+}
+
+impl Default for GappingOptions {
+    fn default() -> Self {
+        GappingOptions {
+            target_gap_meters: 11.0,
+            min_gap_meters: 6.0,
+            max_gap_meters: 18.0,
+            carry_per_loft_degree_meters: 2.5,
+            max_loft_change_degrees: 2.0,
+            min_shots: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GapStatus {
+    Good,
+    Overlap,
+    Hole,
+}
+
+impl GapStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GapStatus::Good => "good",
+            GapStatus::Overlap => "overlap",
+            GapStatus::Hole => "hole",
+        }
+    }
+}
+
+/// Stock distances of one club and the change suggested for it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClubGapping {
+    pub club: String,
+    pub shot_count: usize,
+
+    /// Median carry
+    pub stock_carry_meters: f64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stock_total_meters: Option<f64>,
+
+    /// Middle half of the carries (25th to 75th percentile)
+    pub carry_low_meters: f64,
+    pub carry_high_meters: f64,
+
+    /// Median `club_loft_degrees` of the club's shots, when provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loft_degrees: Option<f64>,
+
+    /// Negative = strengthen (less loft) to carry it farther
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_loft_change_degrees: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_loft_degrees: Option<f64>,
+}
+
+/// Carry gap between two clubs adjacent by stock carry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClubGap {
+    pub longer_club: String,
+    pub shorter_club: String,
+    pub carry_gap_meters: f64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_gap_meters: Option<f64>,
+
+    pub status: GapStatus,
+}
+
+/// Bag gapping built from a session of club-tagged shots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GappingReport {
+    /// Longest stock carry first
+    pub clubs: Vec<ClubGapping>,
+
+    /// `gaps[i]` lies between `clubs[i]` and `clubs[i + 1]`
+    pub gaps: Vec<ClubGap>,
+
+    /// Clubs with fewer than `min_shots` carries
+    pub skipped_clubs: Vec<String>,
+}
+
+/// Stock carry per club, gaps between neighbours, and loft changes that would even them out.
+///
+/// Each overlap or hole is fixed on the shorter club of the pair: the loft change moves its
+/// carry toward `target_gap_meters` below the longer club, counting any change already
+/// suggested for the longer club. Gaps report the bag as hit. Shots without a `club` are ignored.
+pub fn gapping_report(session: &Session, options: &GappingOptions) -> GappingReport {
+    let mut clubs = Vec::new();
+    let mut skipped_clubs = Vec::new();
+    for club in session.clubs().into_iter().flatten() {
+        let carry = session.stats(
+            SessionMetric::CarryDistanceMeters,
+            ClubSelector::Club(&club),
+        );
+        match carry {
+            Some(carry) if carry.count >= options.min_shots.max(1) => {
+                clubs.push(club_gapping(session, club, &carry))
+            }
+            _ => skipped_clubs.push(club),
+        }
+    }
+    clubs.sort_by(|a, b| b.stock_carry_meters.total_cmp(&a.stock_carry_meters));
+
+    let gaps: Vec<ClubGap> = clubs
+        .windows(2)
+        .map(|pair| gap_between(&pair[0], &pair[1], options))
+        .collect();
+
+    // Longest club first: each suggestion is judged against the longer club's carry after its
+    // own suggested change, so two adjacent fixes do not pull against each other
+    let carry_per_degree = options.carry_per_loft_degree_meters.max(f64::EPSILON);
+    for i in 1..clubs.len() {
+        let longer = &clubs[i - 1];
+        let shift = -longer.suggested_loft_change_degrees.unwrap_or(0.0) * carry_per_degree;
+        let adjusted_longer = ClubGapping {
+            stock_carry_meters: longer.stock_carry_meters + shift,
+            carry_low_meters: longer.carry_low_meters + shift,
+            carry_high_meters: longer.carry_high_meters + shift,
+            ..longer.clone()
+        };
+        let gap = gap_between(&adjusted_longer, &clubs[i], options);
+        if gap.status == GapStatus::Good {
+            continue;
+        }
+        let carry_change = gap.carry_gap_meters - options.target_gap_meters;
+        // Ranges can overlap while the median gap is already on target; moving the shorter club
+        // toward the longer one would only make that worse
+        if gap.status == GapStatus::Overlap && carry_change >= 0.0 {
+            continue;
+        }
+        let change = (-carry_change / carry_per_degree).clamp(
+            -options.max_loft_change_degrees,
+            options.max_loft_change_degrees,
+        );
+        // Half-degree steps, as lofts are bent
+        let change = (change * 2.0).round() / 2.0;
+        if change != 0.0 {
+            let shorter = &mut clubs[i];
+            shorter.suggested_loft_change_degrees = Some(change);
+            shorter.suggested_loft_degrees = shorter.loft_degrees.map(|loft| loft + change);
+        }
+    }
+
+    GappingReport {
+        clubs,
+        gaps,
+        skipped_clubs,
+    }
+}
+
+fn club_gapping(session: &Session, club: String, carry: &MetricStats) -> ClubGapping {
+    let median = |metric| {
+        session
+            .stats(metric, ClubSelector::Club(&club))
+            .map(|s| s.median)
+    };
+    let loft = MetricStats::from_values(
+        &session
+            .shots()
+            .iter()
+            .filter(|shot| shot.club.as_deref() == Some(&club))
+            .filter_map(|shot| shot.club_loft_degrees)
+            .collect::<Vec<_>>(),
+    )
+    .map(|s| s.median);
+
+    ClubGapping {
+        shot_count: carry.count,
+        stock_carry_meters: carry.median,
+        stock_total_meters: median(SessionMetric::TotalDistanceMeters),
+        carry_low_meters: carry.p25,
+        carry_high_meters: carry.p75,
+        loft_degrees: loft,
+        suggested_loft_change_degrees: None,
+        suggested_loft_degrees: None,
+        club,
+    }
+}
+
+fn gap_between(longer: &ClubGapping, shorter: &ClubGapping, options: &GappingOptions) -> ClubGap {
+    let carry_gap = longer.stock_carry_meters - shorter.stock_carry_meters;
+    let ranges_overlap = shorter.carry_high_meters >= longer.carry_low_meters;
+    let status = if carry_gap < options.min_gap_meters || ranges_overlap {
+        GapStatus::Overlap
+    } else if carry_gap > options.max_gap_meters {
+        GapStatus::Hole
+    } else {
+        GapStatus::Good
+    };
+
+    ClubGap {
+        longer_club: longer.club.clone(),
+        shorter_club: shorter.club.clone(),
+        carry_gap_meters: carry_gap,
+        total_gap_meters: longer
+            .stock_total_meters
+            .zip(shorter.stock_total_meters)
+            .map(|(a, b)| a - b),
+        status,
+    }
+}
+
+impl GappingReport {
+    /// One row per club, longest first; the gap columns describe the gap to the next shorter club
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "club,shot_count,stock_carry_meters,stock_total_meters,carry_low_meters,\
+             carry_high_meters,gap_to_next_meters,gap_status,loft_degrees,\
+             suggested_loft_change_degrees,suggested_loft_degrees\n",
+        );
+        let number = |value: Option<f64>| value.map(|v| format!("{:.1}", v)).unwrap_or_default();
+        for (i, club) in self.clubs.iter().enumerate() {
+            let gap = self.gaps.get(i);
+            let _ = writeln!(
+                csv,
+                "{},{},{:.1},{},{:.1},{:.1},{},{},{},{},{}",
+                csv_field(&club.club),
+                club.shot_count,
+                club.stock_carry_meters,
+                number(club.stock_total_meters),
+                club.carry_low_meters,
+                club.carry_high_meters,
+                number(gap.map(|g| g.carry_gap_meters)),
+                gap.map(|g| g.status.as_str()).unwrap_or_default(),
+                number(club.loft_degrees),
+                number(club.suggested_loft_change_degrees),
+                number(club.suggested_loft_degrees),
+            );
+        }
+        csv
+    }
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or newline
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DerivedValues;
+
+    fn session(clubs: &[(&str, f64, [f64; 3])]) -> Session {
+        clubs
+            .iter()
+            .flat_map(|&(club, loft, carries)| {
+                carries.map(|carry| {
+                    let mut shot = DerivedValues::new();
+                    shot.club = Some(club.to_string());
+                    shot.club_loft_degrees = Some(loft);
+                    shot.carry_distance_meters = Some(carry);
+                    shot.total_distance_meters = Some(carry + 8.0);
+                    shot
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_gaps_overlaps_and_holes() {
+        let bag = session(&[
+            ("9i", 41.0, [118.0, 120.0, 122.0]),
+            ("7i", 33.0, [140.0, 142.0, 144.0]),
+            ("8i", 37.0, [129.0, 131.0, 133.0]),
+            ("6i", 29.0, [144.0, 146.0, 148.0]),
+            ("5i", 26.0, [168.0, 170.0, 172.0]),
+        ]);
+        let mut lonely = DerivedValues::new();
+        lonely.club = Some("3w".to_string());
+        lonely.carry_distance_meters = Some(200.0);
+        let mut bag_with_extra = bag.clone();
+        bag_with_extra.add(lonely);
+
+        let report = gapping_report(&bag_with_extra, &GappingOptions::default());
+        let order: Vec<_> = report.clubs.iter().map(|c| c.club.as_str()).collect();
+        assert_eq!(order, ["5i", "6i", "7i", "8i", "9i"]);
+        assert_eq!(report.skipped_clubs, ["3w"]);
+
+        let statuses: Vec<_> = report.gaps.iter().map(|g| g.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                GapStatus::Hole,
+                GapStatus::Overlap,
+                GapStatus::Good,
+                GapStatus::Good
+            ]
+        );
+        assert_eq!(report.gaps[0].carry_gap_meters, 24.0);
+        assert_eq!(report.gaps[0].total_gap_meters, Some(24.0));
+
+        // The 6i carries too short of the 5i (strengthen). Strengthened, it carries 151 m,
+        // which also clears the 7i overlap, so the 7i is left alone.
+        assert_eq!(report.clubs[1].suggested_loft_change_degrees, Some(-2.0));
+        assert_eq!(report.clubs[1].suggested_loft_degrees, Some(27.0));
+        assert_eq!(report.clubs[2].suggested_loft_change_degrees, None);
+        assert_eq!(report.clubs[3].suggested_loft_change_degrees, None);
+    }
+
+    #[test]
+    fn test_suggestions_use_adjusted_carry() {
+        let report = gapping_report(
+            &session(&[
+                ("5i", 26.0, [168.0, 170.0, 172.0]),
+                ("6i", 29.0, [144.0, 146.0, 148.0]),
+                ("7i", 33.0, [128.0, 130.0, 132.0]),
+            ]),
+            &GappingOptions::default(),
+        );
+        // As hit, the 6i-7i gap (16 m) is fine; once the 6i is strengthened to carry 151 m
+        // it becomes a 21 m hole, so the 7i is strengthened too
+        assert_eq!(report.gaps[1].status, GapStatus::Good);
+        assert_eq!(report.clubs[1].suggested_loft_change_degrees, Some(-2.0));
+        assert_eq!(report.clubs[2].suggested_loft_change_degrees, Some(-2.0));
+        assert_eq!(report.clubs[2].suggested_loft_degrees, Some(31.0));
+    }
+
+    #[test]
+    fn test_range_only_overlap_not_narrowed() {
+        let report = gapping_report(
+            &session(&[
+                ("6i", 29.0, [140.0, 150.0, 160.0]),
+                ("7i", 33.0, [126.0, 138.0, 154.0]),
+            ]),
+            &GappingOptions::default(),
+        );
+        assert_eq!(report.gaps[0].status, GapStatus::Overlap);
+        assert!(report.gaps[0].carry_gap_meters > 11.0);
+        assert_eq!(report.clubs[1].suggested_loft_change_degrees, None);
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let report = gapping_report(
+            &session(&[
+                ("7 iron", 33.0, [140.0, 142.0, 144.0]),
+                ("PW, std", 45.0, [110.0, 112.0, 114.0]),
+            ]),
+            &GappingOptions::default(),
+        );
+        let csv = report.to_csv();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("club,shot_count,stock_carry_meters"));
+        assert!(lines[1].starts_with("7 iron,3,142.0,150.0,"));
+        assert!(lines[1].contains(",30.0,hole,33.0,"));
+        assert!(lines[2].starts_with("\"PW, std\",3,112.0"));
+        assert_eq!(lines[2].split(',').count(), lines[0].split(',').count() + 1);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::impact::BallLaunch;
+use crate::vector::Vector3;
+use crate::{DerivedValues, InputData, InputUSCustomaryUnits, USCustomaryValues};
+use serde::{Deserialize, Serialize};
+
+/// Which side of the ball the golfer stands on
+///
+/// Launch monitors report lateral values in a fixed world frame (positive = right of target).
+/// For a left-handed golfer the core mirrors that frame so shot names and club delivery are
+/// expressed from the golfer's point of view: a lefty curving the ball right hits a draw,
+/// and a positive club path is still in-to-out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Handedness {
+    #[default]
+    Right,
+    Left,
+}
+
+fn mirror_vector(vector: &mut Option<Vector3>) {
+    if let Some(v) = vector {
+        v.y = -v.y;
+    }
+}
+
+fn negate(value: &mut Option<f64>) {
+    if let Some(v) = value {
+        *v = -*v;
+    }
+}
+
+fn mirror_input_units(units: &mut InputUSCustomaryUnits) {
+    negate(&mut units.offline_distance_yards);
+    mirror_vector(&mut units.landing_position_yards);
+    mirror_vector(&mut units.landing_velocity_mph);
+}
+
+fn mirror_output_units(units: &mut USCustomaryValues) {
+    negate(&mut units.offline_distance_yards);
+    mirror_vector(&mut units.landing_position_yards);
+    mirror_vector(&mut units.landing_velocity_mph);
+}
+
+fn mirror_launch(launch: &mut BallLaunch) {
+    launch.horizontal_launch_angle_degrees = -launch.horizontal_launch_angle_degrees;
+    launch.spin_axis_degrees = -launch.spin_axis_degrees;
+}
+
+impl InputData {
+    /// Input with world-frame lateral values mirrored left/right.
+    ///
+    /// Club path, face angles and strike location are already golfer-relative and are left as-is.
+    pub(crate) fn mirrored(&self) -> InputData {
+        let mut input = self.clone();
+        negate(&mut input.horizontal_launch_angle_degrees);
+        negate(&mut input.spin_axis_degrees);
+        negate(&mut input.sidespin_rpm);
+        negate(&mut input.offline_distance_yards);
+        negate(&mut input.offline_distance_meters);
+        negate(&mut input.target_bearing_degrees);
+        mirror_vector(&mut input.landing_position_yards);
+        mirror_vector(&mut input.landing_velocity_mph);
+        mirror_vector(&mut input.landing_position);
+        mirror_vector(&mut input.landing_velocity);
+        if let Some(units) = input.us_customary_units.as_mut() {
+            mirror_input_units(units);
+        }
+        input
+    }
+}
+
+impl DerivedValues {
+    /// Mirror world-frame lateral outputs left/right (inverse of [`InputData::mirrored`])
+    pub(crate) fn mirror_lateral(&mut self) {
+        negate(&mut self.spin_axis_degrees);
+        negate(&mut self.sidespin_rpm);
+        negate(&mut self.offline_distance_meters);
+        mirror_vector(&mut self.landing_position);
+        mirror_vector(&mut self.landing_velocity);
+        if let Some(launch) = self.predicted_ball_launch.as_mut() {
+            mirror_launch(launch);
+        }
+        if let Some(units) = self.us_customary_units.as_mut() {
+            mirror_output_units(units);
+        }
+    }
+}
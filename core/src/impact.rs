@@ -0,0 +1,416 @@
+use crate::calculate_spin_components;
+use crate::clubhead_data::expected_smash_factor;
+use crate::d_plane::calculate_d_plane_launch;
+use crate::impact_bands::{band_for_ball_speed, ImpactBand};
+use crate::trajectory::{calculate_trajectory, Trajectory};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Impact model constants
+const SOLVER_ITERATIONS: usize = 8; // Fixed-point passes to settle the speed band
+const INITIAL_SMASH_GUESS: f64 = 1.45;
+const OFFSET_SPEED_LOSS_PER_MM2: f64 = 0.0004; // ~6.5% ball speed lost 1/2" from center
+const MIN_SPEED_RETENTION: f64 = 0.6;
+const HORIZONTAL_GEAR_AXIS_DEG_PER_MM: f64 = 0.6; // Driver: 10mm toe ≈ 6° of hook axis
+const VERTICAL_GEAR_SPIN_PER_MM: f64 = 0.012; // Driver: 10mm high ≈ 12% less spin
+const VERTICAL_GEAR_LAUNCH_DEG_PER_MM: f64 = 0.08; // Driver: 10mm high ≈ +0.8° launch
+const MIN_GEAR_GAIN_FOR_INFERENCE: f64 = 0.75; // Only woods show reliable gear effect
+const MAX_INFERRED_OFFSET_MM: f64 = 30.0; // Roughly the edge of a driver face
+const SMASH_DEFICIT_FULL_CONFIDENCE: f64 = 0.08; // 8% speed loss is a clear mishit
+
+/// Club delivery at impact, the input to the forward impact model
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ClubDelivery {
+    pub club_speed_meters_per_second: f64,
+
+    #[serde(default)]
+    pub club_path_degrees: f64,
+
+    #[serde(default)]
+    pub club_face_to_target_degrees: f64,
+
+    #[serde(default)]
+    pub attack_angle_degrees: f64,
+
+    pub dynamic_loft_degrees: f64,
+
+    /// Strike location across the face (positive = toe, negative = heel)
+    #[serde(default)]
+    pub impact_offset_horizontal_millimeters: f64,
+
+    /// Strike location up the face (positive = above center)
+    #[serde(default)]
+    pub impact_offset_vertical_millimeters: f64,
+}
+
+/// Ball launch conditions, as a launch monitor would report them
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BallLaunch {
+    pub ball_speed_meters_per_second: f64,
+    pub vertical_launch_angle_degrees: f64,
+    pub horizontal_launch_angle_degrees: f64,
+    pub total_spin_rpm: f64,
+    pub spin_axis_degrees: f64,
+}
+
+/// Predicted launch together with the resulting ball flight
+#[derive(Debug, Clone)]
+pub struct SimulatedShot {
+    pub launch: BallLaunch,
+    pub trajectory: Trajectory,
+}
+
+/// Where on the face the ball was struck, with how sure we are about it
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ImpactLocationEstimate {
+    /// Strike location across the face (positive = toe, negative = heel)
+    pub horizontal_millimeters: f64,
+
+    /// Strike location up the face (positive = above center)
+    pub vertical_millimeters: f64,
+
+    /// 0 = pure guess, 1 = measured
+    pub confidence: f64,
+}
+
+/// Fraction of centered-strike ball speed retained for an off-center hit
+pub(crate) fn offset_speed_retention(horizontal_mm: f64, vertical_mm: f64) -> f64 {
+    let distance_sq = horizontal_mm.powi(2) + vertical_mm.powi(2);
+    (1.0 - OFFSET_SPEED_LOSS_PER_MM2 * distance_sq).max(MIN_SPEED_RETENTION)
+}
+
+/// Spin axis change from horizontal gear effect (toe → hook spin, heel → slice spin)
+pub(crate) fn gear_effect_spin_axis_shift(band: &ImpactBand, horizontal_mm: f64) -> f64 {
+    -HORIZONTAL_GEAR_AXIS_DEG_PER_MM * band.gear_effect_gain * horizontal_mm
+}
+
+/// Spin multiplier from vertical gear effect (high on the face → less spin)
+fn gear_effect_spin_scale(band: &ImpactBand, vertical_mm: f64) -> f64 {
+    (1.0 - VERTICAL_GEAR_SPIN_PER_MM * band.gear_effect_gain * vertical_mm).max(0.2)
+}
+
+/// Spin axis change (degrees) that horizontal gear effect adds for a strike location.
+///
+/// Subtracting this from a measured spin axis leaves the part explained by face-to-path.
+///
+/// # Arguments
+/// * `ball_speed_mps` - Ball speed in meters per second (selects the club band)
+/// * `impact_offset_horizontal_mm` - Strike location across the face (positive = toe)
+///
+/// # Returns
+/// Spin axis shift in degrees (negative = toward hook)
+pub fn gear_effect_spin_axis_degrees(ball_speed_mps: f64, impact_offset_horizontal_mm: f64) -> f64 {
+    let band = band_for_ball_speed(ball_speed_mps.max(5.0));
+    gear_effect_spin_axis_shift(&band, impact_offset_horizontal_mm)
+}
+
+/// Infer the likely strike location for drivers and woods.
+///
+/// The distance from center comes from the smash factor deficit against the expected
+/// smash for the launch conditions. Spin well below or above the band optimum points to
+/// a high or low strike, and the rest of the distance is assigned across the face. Toe
+/// and heel are told apart by spin axis anomalies: a ball that starts right but hooks
+/// (or starts left but slices) is the classic gear-effect signature.
+///
+/// Only meaningful when club speed was measured rather than estimated.
+///
+/// # Arguments
+/// * `ball_speed_mps` - Ball speed in meters per second
+/// * `club_speed_mps` - Measured clubhead speed in meters per second
+/// * `vertical_launch_angle_deg` - Vertical launch angle in degrees
+/// * `horizontal_launch_angle_deg` - Horizontal launch angle in degrees
+/// * `total_spin_rpm` - Total spin rate in RPM
+/// * `spin_axis_degrees` - Spin axis in degrees (positive = fade/slice)
+///
+/// # Returns
+/// Estimated impact location, or None for irons/wedges or unusable inputs
+pub fn estimate_impact_location(
+    ball_speed_mps: f64,
+    club_speed_mps: f64,
+    vertical_launch_angle_deg: f64,
+    horizontal_launch_angle_deg: f64,
+    total_spin_rpm: f64,
+    spin_axis_degrees: f64,
+) -> Option<ImpactLocationEstimate> {
+    if club_speed_mps <= 0.0 || ball_speed_mps <= 0.0 {
+        return None;
+    }
+    let band = band_for_ball_speed(ball_speed_mps.max(5.0));
+    if band.gear_effect_gain < MIN_GEAR_GAIN_FOR_INFERENCE {
+        return None;
+    }
+
+    let expected = expected_smash_factor(ball_speed_mps, vertical_launch_angle_deg, total_spin_rpm);
+    let retention = (ball_speed_mps / club_speed_mps) / expected;
+    let deficit = (1.0 - retention).max(0.0);
+    let distance_mm = (deficit / OFFSET_SPEED_LOSS_PER_MM2)
+        .sqrt()
+        .min(MAX_INFERRED_OFFSET_MM);
+
+    // High strikes spin less, low strikes spin more. Spin inside the band's normal
+    // window has too many other causes to say anything about strike height.
+    let spin = total_spin_rpm.max(0.0);
+    let spin_window = spin.clamp(
+        band.optimal_spin_rpm - band.spin_tolerance_rpm,
+        band.optimal_spin_rpm + band.spin_tolerance_rpm,
+    );
+    let spin_excess_ratio = (spin - spin_window) / band.optimal_spin_rpm;
+    let vertical_mm = (-spin_excess_ratio / (VERTICAL_GEAR_SPIN_PER_MM * band.gear_effect_gain))
+        .clamp(-distance_mm, distance_mm);
+    let across_mm = (distance_mm.powi(2) - vertical_mm.powi(2)).max(0.0).sqrt();
+
+    // Start direction opposite the curve is the gear-effect signature; a curve alone
+    // only weakly suggests a side.
+    let gear_signature = (horizontal_launch_angle_deg >= 0.0 && spin_axis_degrees < 0.0)
+        || (horizontal_launch_angle_deg <= 0.0 && spin_axis_degrees > 0.0);
+    let side_weight = if gear_signature { 0.8 } else { 0.4 };
+    let horizontal_mm = if spin_axis_degrees < 0.0 {
+        across_mm
+    } else if spin_axis_degrees > 0.0 {
+        -across_mm
+    } else {
+        0.0
+    };
+
+    let confidence = (deficit / SMASH_DEFICIT_FULL_CONFIDENCE).min(1.0) * side_weight;
+
+    Some(ImpactLocationEstimate {
+        horizontal_millimeters: horizontal_mm,
+        vertical_millimeters: vertical_mm,
+        confidence,
+    })
+}
+
+/// Predict ball launch from club delivery.
+///
+/// The reverse direction of [`crate::estimate_clubhead_speed`]: launch direction and
+/// spin axis come from the 3D D-plane, spin rate from spin loft and ball speed, and
+/// ball speed from the same collision model used to estimate club speed. Because ball
+/// speed selects the club band, the solution is iterated to a fixed point.
+///
+/// Off-center strikes lose ball speed and pick up gear effect: toe hits add hook spin,
+/// heel hits add slice spin, and high strikes launch higher with less spin. Gear effect
+/// is strongest for woods and fades toward the wedges.
+///
+/// # Arguments
+/// * `delivery` - Club speed, path, face, attack angle, dynamic loft and impact offset
+///
+/// # Returns
+/// Predicted ball speed, launch angles, total spin and spin axis
+pub fn simulate_impact(delivery: &ClubDelivery) -> BallLaunch {
+    let club_speed = delivery.club_speed_meters_per_second.max(0.0);
+    let speed_retention = offset_speed_retention(
+        delivery.impact_offset_horizontal_millimeters,
+        delivery.impact_offset_vertical_millimeters,
+    );
+
+    let mut launch = BallLaunch {
+        ball_speed_meters_per_second: club_speed * INITIAL_SMASH_GUESS * speed_retention,
+        vertical_launch_angle_degrees: delivery.dynamic_loft_degrees,
+        horizontal_launch_angle_degrees: delivery.club_face_to_target_degrees,
+        total_spin_rpm: 0.0,
+        spin_axis_degrees: 0.0,
+    };
+
+    for _ in 0..SOLVER_ITERATIONS {
+        let ball_speed = launch.ball_speed_meters_per_second.max(5.0);
+        let band = band_for_ball_speed(ball_speed);
+
+        let d_plane = calculate_d_plane_launch(
+            delivery.club_path_degrees,
+            delivery.club_face_to_target_degrees,
+            delivery.attack_angle_degrees,
+            delivery.dynamic_loft_degrees,
+            band.launch_loft_ratio,
+        );
+
+        let spin_loft_rad = d_plane.spin_loft_degrees * PI / 180.0;
+        let centered_spin = band.spin_loft_gain * ball_speed * spin_loft_rad.sin();
+
+        launch.total_spin_rpm = centered_spin
+            * gear_effect_spin_scale(&band, delivery.impact_offset_vertical_millimeters);
+        launch.spin_axis_degrees = d_plane.spin_axis_degrees
+            + gear_effect_spin_axis_shift(&band, delivery.impact_offset_horizontal_millimeters);
+        launch.horizontal_launch_angle_degrees = d_plane.horizontal_launch_angle_degrees;
+        launch.vertical_launch_angle_degrees = d_plane.vertical_launch_angle_degrees
+            + VERTICAL_GEAR_LAUNCH_DEG_PER_MM
+                * band.gear_effect_gain
+                * delivery.impact_offset_vertical_millimeters;
+
+        let smash = expected_smash_factor(
+            ball_speed,
+            launch.vertical_launch_angle_degrees,
+            launch.total_spin_rpm,
+        );
+        launch.ball_speed_meters_per_second = club_speed * smash * speed_retention;
+    }
+
+    launch
+}
+
+/// Predict ball launch from club delivery and fly the resulting trajectory.
+///
+/// # Arguments
+/// * `delivery` - Club speed, path, face, attack angle, dynamic loft and impact offset
+/// * `elevation_m` - Altitude in meters above sea level
+/// * `temperature_k` - Temperature in Kelvin
+/// * `humidity_percent` - Relative humidity as percentage (0-100)
+/// * `pressure_pa` - Atmospheric pressure in Pascals (optional, calculated from elevation if None)
+///
+/// # Returns
+/// Predicted launch conditions and the full trajectory
+pub fn simulate_shot(
+    delivery: &ClubDelivery,
+    elevation_m: f64,
+    temperature_k: f64,
+    humidity_percent: f64,
+    pressure_pa: Option<f64>,
+) -> SimulatedShot {
+    let launch = simulate_impact(delivery);
+    let (backspin, sidespin) =
+        calculate_spin_components(launch.total_spin_rpm, launch.spin_axis_degrees);
+
+    let trajectory = calculate_trajectory(
+        launch.ball_speed_meters_per_second,
+        launch.vertical_launch_angle_degrees,
+        launch.horizontal_launch_angle_degrees,
+        backspin,
+        sidespin,
+        elevation_m,
+        temperature_k,
+        humidity_percent,
+        pressure_pa,
+    );
+
+    SimulatedShot { launch, trajectory }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{estimate_clubhead_speed, get_carry_distance, get_offline_distance};
+
+    fn driver_delivery() -> ClubDelivery {
+        ClubDelivery {
+            club_speed_meters_per_second: 47.8, // ~107 mph
+            club_path_degrees: 0.0,
+            club_face_to_target_degrees: 0.0,
+            attack_angle_degrees: 2.0,
+            dynamic_loft_degrees: 14.0,
+            impact_offset_horizontal_millimeters: 0.0,
+            impact_offset_vertical_millimeters: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_driver_impact_is_realistic() {
+        let launch = simulate_impact(&driver_delivery());
+        let smash = launch.ball_speed_meters_per_second / 47.8;
+        assert!(
+            smash > 1.38 && smash < 1.52,
+            "Driver smash factor {} should be realistic",
+            smash
+        );
+        assert!(
+            launch.vertical_launch_angle_degrees > 10.0
+                && launch.vertical_launch_angle_degrees < 14.0
+        );
+        assert!(
+            launch.total_spin_rpm > 1800.0 && launch.total_spin_rpm < 3500.0,
+            "Driver spin {} should be realistic",
+            launch.total_spin_rpm
+        );
+        assert!(launch.spin_axis_degrees.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_impact_round_trips_clubhead_speed() {
+        let delivery = driver_delivery();
+        let launch = simulate_impact(&delivery);
+        let estimated = estimate_clubhead_speed(
+            launch.ball_speed_meters_per_second,
+            launch.vertical_launch_angle_degrees,
+            launch.total_spin_rpm,
+        );
+        assert!(
+            (estimated - delivery.club_speed_meters_per_second).abs() < 0.5,
+            "Estimated club speed {} should match delivered {}",
+            estimated,
+            delivery.club_speed_meters_per_second
+        );
+    }
+
+    #[test]
+    fn test_toe_strike_loses_speed_and_adds_hook_spin() {
+        let centered = simulate_impact(&driver_delivery());
+        let toe = simulate_impact(&ClubDelivery {
+            impact_offset_horizontal_millimeters: 12.0,
+            ..driver_delivery()
+        });
+        assert!(toe.ball_speed_meters_per_second < centered.ball_speed_meters_per_second);
+        assert!(toe.spin_axis_degrees < -3.0);
+    }
+
+    #[test]
+    fn test_impact_location_recovers_toe_strike() {
+        let toe = simulate_impact(&ClubDelivery {
+            club_path_degrees: 2.0,
+            club_face_to_target_degrees: 2.0,
+            impact_offset_horizontal_millimeters: 15.0,
+            ..driver_delivery()
+        });
+        let estimate = estimate_impact_location(
+            toe.ball_speed_meters_per_second,
+            47.8,
+            toe.vertical_launch_angle_degrees,
+            toe.horizontal_launch_angle_degrees,
+            toe.total_spin_rpm,
+            toe.spin_axis_degrees,
+        )
+        .unwrap();
+        assert!(
+            estimate.horizontal_millimeters > 5.0,
+            "Toe strike should be inferred, got {}",
+            estimate.horizontal_millimeters
+        );
+        assert!(estimate.confidence > 0.5);
+    }
+
+    #[test]
+    fn test_impact_location_centered_strike_has_low_confidence() {
+        let centered = simulate_impact(&driver_delivery());
+        let estimate = estimate_impact_location(
+            centered.ball_speed_meters_per_second,
+            47.8,
+            centered.vertical_launch_angle_degrees,
+            centered.horizontal_launch_angle_degrees,
+            centered.total_spin_rpm,
+            centered.spin_axis_degrees,
+        )
+        .unwrap();
+        assert!(estimate.confidence < 0.2);
+    }
+
+    #[test]
+    fn test_impact_location_skips_irons() {
+        assert!(estimate_impact_location(45.0, 34.0, 18.0, 1.0, 6500.0, -5.0).is_none());
+    }
+
+    #[test]
+    fn test_open_face_simulated_shot_finishes_right() {
+        let shot = simulate_shot(
+            &ClubDelivery {
+                club_path_degrees: -2.0,
+                club_face_to_target_degrees: 2.0,
+                ..driver_delivery()
+            },
+            0.0,
+            298.15,
+            50.0,
+            None,
+        );
+        assert!(shot.launch.spin_axis_degrees > 0.0);
+        assert!(get_carry_distance(&shot.trajectory) > 150.0);
+        assert!(get_offline_distance(&shot.trajectory) > 5.0);
+    }
+}
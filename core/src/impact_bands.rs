@@ -0,0 +1,194 @@
+use crate::config_file::{load_file, parse_toml, ConfigError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+static IMPACT_BANDS: OnceLock<RwLock<Vec<ImpactBand>>> = OnceLock::new();
+
+const DATA: &str = "impact bands";
+
+macro_rules! include_impact_bands {
+    () => {
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../shot_classification/impact_bands.toml"
+        ))
+    };
+}
+
+/// Club category parameters used by the clubhead estimates, keyed by ball speed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ImpactBand {
+    pub max_ball_speed_mps: f64,
+    pub base_cor: f64,
+    pub optimal_launch_deg: f64,
+    pub launch_tolerance_deg: f64,
+    pub optimal_spin_rpm: f64,
+    pub spin_tolerance_rpm: f64,
+    pub face_influence_ratio: f64,
+    pub spin_axis_gain: f64,
+    pub launch_loft_ratio: f64,
+    pub spin_loft_gain: f64,
+    pub gear_effect_gain: f64,
+}
+
+#[derive(Deserialize)]
+struct ImpactBandsFile {
+    bands: Vec<ImpactBand>,
+}
+
+fn validate_bands(bands: &[ImpactBand]) -> Result<(), ConfigError> {
+    let last = bands
+        .last()
+        .ok_or_else(|| ConfigError::invalid(DATA, "at least one band is required"))?;
+    if last.max_ball_speed_mps != f64::INFINITY {
+        return Err(ConfigError::invalid(
+            DATA,
+            "last band must use max_ball_speed_mps = inf",
+        ));
+    }
+
+    for (index, band) in bands.iter().enumerate() {
+        let fail = |msg: &str| {
+            Err(ConfigError::invalid(
+                DATA,
+                format!("band {}: {}", index, msg),
+            ))
+        };
+
+        if index > 0 && band.max_ball_speed_mps <= bands[index - 1].max_ball_speed_mps {
+            return fail("max_ball_speed_mps must increase from band to band");
+        }
+        if !(0.0..=1.0).contains(&band.base_cor) {
+            return fail("base_cor must be between 0 and 1");
+        }
+        if band.launch_tolerance_deg <= 0.0 || band.spin_tolerance_rpm <= 0.0 {
+            return fail("tolerances must be positive");
+        }
+        if !(0.0..=1.0).contains(&band.face_influence_ratio)
+            || !(0.0..=1.0).contains(&band.launch_loft_ratio)
+        {
+            return fail("face_influence_ratio and launch_loft_ratio must be between 0 and 1");
+        }
+        if band.spin_axis_gain <= 0.0 || band.spin_loft_gain <= 0.0 {
+            return fail("spin_axis_gain and spin_loft_gain must be positive");
+        }
+        if band.optimal_spin_rpm <= 0.0 || band.gear_effect_gain < 0.0 {
+            return fail("optimal_spin_rpm must be positive and gear_effect_gain non-negative");
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse and validate impact bands from TOML text (`[[bands]]` tables ordered by ball speed)
+pub fn load_impact_bands_from_str(toml_text: &str) -> Result<Vec<ImpactBand>, ConfigError> {
+    let file: ImpactBandsFile = parse_toml(DATA, toml_text)?;
+    validate_bands(&file.bands)?;
+    Ok(file.bands)
+}
+
+/// Parse and validate impact bands from a TOML file on disk
+pub fn load_impact_bands_from_file(path: impl AsRef<Path>) -> Result<Vec<ImpactBand>, ConfigError> {
+    load_file(path.as_ref(), load_impact_bands_from_str)
+}
+
+/// Impact bands shipped with the library
+pub fn default_impact_bands() -> Vec<ImpactBand> {
+    load_impact_bands_from_str(include_impact_bands!())
+        .expect("embedded impact_bands.toml must be valid")
+}
+
+fn bands_lock() -> &'static RwLock<Vec<ImpactBand>> {
+    IMPACT_BANDS.get_or_init(|| RwLock::new(default_impact_bands()))
+}
+
+/// Impact bands currently used by the clubhead estimates
+pub fn impact_bands() -> Vec<ImpactBand> {
+    bands_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Replace the impact bands used by all subsequent calculations
+pub fn set_impact_bands(bands: Vec<ImpactBand>) -> Result<(), ConfigError> {
+    validate_bands(&bands)?;
+    *bands_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = bands;
+    Ok(())
+}
+
+/// Restore the impact bands shipped with the library
+pub fn reset_impact_bands() {
+    *bands_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = default_impact_bands();
+}
+
+pub(crate) fn band_for_ball_speed(ball_speed_mps: f64) -> ImpactBand {
+    let bands = bands_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    for band in bands.iter() {
+        if ball_speed_mps <= band.max_ball_speed_mps {
+            return *band;
+        }
+    }
+    bands[bands.len() - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_bands_load() {
+        let bands = default_impact_bands();
+        assert_eq!(bands.len(), 4);
+        assert_eq!(bands[0].max_ball_speed_mps, 40.0);
+        assert_eq!(bands[3].max_ball_speed_mps, f64::INFINITY);
+        assert_eq!(bands[3].base_cor, 0.83);
+    }
+
+    #[test]
+    fn test_band_lookup_by_speed() {
+        assert_eq!(band_for_ball_speed(35.0).optimal_spin_rpm, 9000.0);
+        assert_eq!(band_for_ball_speed(55.0).optimal_spin_rpm, 5000.0);
+        assert_eq!(band_for_ball_speed(75.0).optimal_spin_rpm, 2500.0);
+    }
+
+    #[test]
+    fn test_rejects_unordered_bands() {
+        let mut text = include_impact_bands!().to_string();
+        text = text.replacen("max_ball_speed_mps = 50.0", "max_ball_speed_mps = 30.0", 1);
+        assert!(matches!(
+            load_impact_bands_from_str(&text),
+            Err(ConfigError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_missing_field() {
+        let text = r#"
+            [[bands]]
+            max_ball_speed_mps = inf
+            base_cor = 0.8
+        "#;
+        assert!(matches!(
+            load_impact_bands_from_str(text),
+            Err(ConfigError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_finite_last_band() {
+        let text = include_impact_bands!()
+            .replace("max_ball_speed_mps = inf", "max_ball_speed_mps = 90.0");
+        assert!(matches!(
+            load_impact_bands_from_str(&text),
+            Err(ConfigError::Invalid { .. })
+        ));
+    }
+}
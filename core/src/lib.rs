@@ -1,8 +1,29 @@
 // Core modules
+mod ball_model;
+mod batch;
+mod benchmarks;
+mod calibration;
+mod classifier_config;
 mod clubhead_data;
+mod coaching;
+mod config_file;
+mod contact_quality;
+mod d_plane;
+mod dispersion;
+mod gapping;
+mod handedness;
+mod impact;
+mod impact_bands;
+mod localization;
+mod outcome_classifier;
+mod rank_scheme;
+mod reference_classifier;
+mod session;
 mod shot_classifier;
+mod shot_store;
 mod trajectory;
 mod trajectory_analysis;
+mod trends;
 mod unit_conversions;
 mod vector;
 
@@ -10,22 +31,100 @@ mod vector;
 pub mod bindings;
 
 // Re-export bindings at crate root for compatibility
-pub use bindings::{calculate_derived_values, calculate_derived_values_ffi};
+pub use bindings::{
+    calculate_derived_values, calculate_derived_values_batch, calculate_derived_values_ffi,
+};
 
 // Re-export public Rust API types
+pub use ball_model::{
+    ball_model, load_ball_model_from_file, load_ball_model_from_str, reset_ball_model,
+    save_ball_model_to_file, set_ball_model, BallModel,
+};
+pub use batch::{
+    calculate_batch, calculate_batch_json, calculate_batch_values, BatchFormat, BatchItemError,
+    BatchOptions, BatchResults,
+};
+pub use benchmarks::{
+    benchmarks, compare_to_benchmark, default_benchmarks, load_benchmarks_from_file,
+    load_benchmarks_from_str, normalize_club, reset_benchmarks, set_benchmarks, BenchmarkBand,
+    BenchmarkComparison, BenchmarkError, BenchmarkTables, ClubBenchmark, MetricComparison,
+};
+pub use calibration::{
+    calibrate_ball_model, CalibrationOptions, CalibrationResult, CalibrationShot, ResidualStats,
+};
+pub use classifier_config::{
+    classifier_config, load_classifier_config_from_file, load_classifier_config_from_str,
+    reset_classifier_config, set_classifier_config, ClassifierConfig, SkillLevel,
+};
 pub use clubhead_data::{
-    estimate_club_face_path, estimate_clubhead_speed, get_smash_factor, ClubFacePathEstimates,
+    estimate_club_face_path, estimate_club_loft_attack, estimate_clubhead_speed,
+    estimate_d_plane_delivery, get_smash_factor, ClubFacePathEstimates, ClubLoftAttackEstimates,
+};
+pub use coaching::{coaching_recommendations, Recommendation, RecommendationId};
+pub use config_file::ConfigError;
+pub use contact_quality::{detect_contact_quality, ContactQuality};
+pub use d_plane::{
+    calculate_d_plane_delivery, calculate_d_plane_launch, DPlaneClubDelivery, DPlaneLaunch,
+};
+pub use dispersion::{
+    analyze_dispersion, landing_point, DepthBias, DispersionAnalysis, DispersionBias,
+    DispersionEllipse, DispersionOptions, DispersionOutlier, LandingPoint, LateralBias,
+};
+pub use gapping::{gapping_report, ClubGap, ClubGapping, GapStatus, GappingOptions, GappingReport};
+pub use handedness::Handedness;
+pub use impact::{
+    estimate_impact_location, gear_effect_spin_axis_degrees, simulate_impact, simulate_shot,
+    BallLaunch, ClubDelivery, ImpactLocationEstimate, SimulatedShot,
+};
+pub use impact_bands::{
+    default_impact_bands, impact_bands, load_impact_bands_from_file, load_impact_bands_from_str,
+    reset_impact_bands, set_impact_bands, ImpactBand,
+};
+pub use localization::{
+    available_locales, define_field, describe_recommendation, describe_shot, load_locale_directory,
+    load_locale_from_str, reset_locales, RecommendationText, ShotDescription, DEFAULT_LOCALE,
+};
+pub use rank_scheme::{
+    color_theme, color_theme_names, default_color_theme, default_rank_scheme,
+    load_color_theme_from_file, load_color_theme_from_str, load_color_themes_from_file,
+    load_color_themes_from_str, load_rank_schemes_from_file, load_rank_schemes_from_str,
+    rank_scheme, rank_scheme_names, register_color_theme, register_rank_scheme, reset_color_themes,
+    reset_rank_schemes, ColorTheme, RankScheme, ShapeRanks, CLASSIC_COLOR_THEME,
+};
+pub use reference_classifier::{
+    default_reference_library, load_reference_library_from_file, load_reference_library_from_str,
+    reference_library, reset_reference_library, set_reference_library, FeatureScales,
+    ReferenceLibrary, ReferenceMatch, ReferenceNeighbor, ReferenceShot, ShotClassifierKind,
+    ShotFeatures,
+};
+pub use session::{
+    ClubSelector, ClubStats, MetricStats, Session, SessionMetric, SessionSummary,
+    REPORTED_PERCENTILES,
+};
+pub use shot_classifier::{
+    classify_shot, ClassificationOptions, Direction, Shape, ShotAlternative,
+    ShotClassificationResult, ShotId,
+};
+pub use shot_store::{
+    ExportFormat, ShotQuery, ShotStore, ShotStoreError, ShotTags, StoredShot,
+    SHOT_STORE_SCHEMA_VERSION,
+};
+pub use trajectory::{
+    calculate_trajectory, calculate_trajectory_with_model, Trajectory, TrajectoryPoint,
 };
-pub use trajectory::{calculate_trajectory, Trajectory, TrajectoryPoint};
 pub use trajectory_analysis::{
     get_apex_position, get_carry_distance, get_descent_angle, get_hang_time, get_landing_position,
-    get_landing_velocity, get_offline_distance, get_peak_height, get_time_to_apex,
-    get_total_distance,
+    get_landing_velocity, get_offline_distance, get_offline_distance_from_target_line,
+    get_peak_height, get_resting_position, get_time_to_apex, get_total_distance,
 };
+pub use trends::{analyze_trends, TrendDirection, TrendFinding, TrendOptions};
 pub use vector::Vector3;
 
+use outcome_classifier::{
+    blend_ranks, classify_outcome, default_target_tolerance, DEFAULT_OUTCOME_WEIGHT,
+};
+use reference_classifier::classify_with_reference_library;
 use serde::{Deserialize, Serialize};
-use shot_classifier::classify_shot;
 use std::f64::consts::PI;
 use unit_conversions::{
     meters_per_second_to_mph, meters_to_yards, mph_to_meters_per_second, vector_meters_to_yards,
@@ -39,6 +138,22 @@ use serde_json::Value;
 /// Derived values calculated by OpenGolfCoach
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DerivedValues {
+    // Shot context
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub club: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ball_speed_meters_per_second: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vertical_launch_angle_degrees: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub club_loft_degrees: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_distance_meters: Option<f64>,
+
     // Spin conversions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub backspin_rpm: Option<f64>,
@@ -99,6 +214,24 @@ pub struct DerivedValues {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub club_face_to_path_degrees: Option<f64>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attack_angle_degrees: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dynamic_loft_degrees: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spin_loft_degrees: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impact_location_estimate: Option<ImpactLocationEstimate>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_quality: Option<ContactQuality>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shot_id: Option<ShotId>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shot_name: Option<String>,
 
@@ -108,6 +241,42 @@ pub struct DerivedValues {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shot_color_rgb: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shot_confidence: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shot_alternatives: Option<Vec<ShotAlternative>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_match: Option<ReferenceMatch>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub localized_shot_name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shot_description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shot_coaching_tip: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recommendations: Option<Vec<Recommendation>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome_name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome_rank: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combined_rank: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance_to_target_meters: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub predicted_ball_launch: Option<BallLaunch>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub us_customary_units: Option<USCustomaryValues>,
 
@@ -185,6 +354,11 @@ pub struct InputUSCustomaryUnits {
 impl DerivedValues {
     fn new() -> Self {
         DerivedValues {
+            club: None,
+            ball_speed_meters_per_second: None,
+            vertical_launch_angle_degrees: None,
+            club_loft_degrees: None,
+            target_distance_meters: None,
             backspin_rpm: None,
             sidespin_rpm: None,
             total_spin_rpm: None,
@@ -204,9 +378,27 @@ impl DerivedValues {
             club_path_degrees: None,
             club_face_to_target_degrees: None,
             club_face_to_path_degrees: None,
+            attack_angle_degrees: None,
+            dynamic_loft_degrees: None,
+            spin_loft_degrees: None,
+            impact_location_estimate: None,
+            contact_quality: None,
+            shot_id: None,
             shot_name: None,
             shot_rank: None,
             shot_color_rgb: None,
+            shot_confidence: None,
+            shot_alternatives: None,
+            reference_match: None,
+            localized_shot_name: None,
+            shot_description: None,
+            shot_coaching_tip: None,
+            recommendations: None,
+            outcome_name: None,
+            outcome_rank: None,
+            combined_rank: None,
+            distance_to_target_meters: None,
+            predicted_ball_launch: None,
             us_customary_units: None,
             pressure_pascals: None,
             elevation_meters: None,
@@ -430,6 +622,33 @@ pub struct InputData {
     #[serde(default)]
     club_face_to_path_degrees: Option<f64>,
 
+    #[serde(default)]
+    attack_angle_degrees: Option<f64>,
+
+    #[serde(default)]
+    dynamic_loft_degrees: Option<f64>,
+
+    #[serde(default)]
+    spin_loft_degrees: Option<f64>,
+
+    #[serde(default)]
+    club_loft_degrees: Option<f64>,
+
+    #[serde(default)]
+    impact_offset_horizontal_millimeters: Option<f64>,
+
+    #[serde(default)]
+    impact_offset_vertical_millimeters: Option<f64>,
+
+    #[serde(default)]
+    impact_location_estimate: Option<ImpactLocationEstimate>,
+
+    #[serde(default)]
+    contact_quality: Option<ContactQuality>,
+
+    #[serde(default)]
+    shot_id: Option<ShotId>,
+
     #[serde(default)]
     shot_name: Option<String>,
 
@@ -439,6 +658,84 @@ pub struct InputData {
     #[serde(default)]
     shot_color_rgb: Option<String>,
 
+    #[serde(default)]
+    shot_confidence: Option<f64>,
+
+    #[serde(default)]
+    shot_alternatives: Option<Vec<ShotAlternative>>,
+
+    #[serde(default)]
+    reference_match: Option<ReferenceMatch>,
+
+    #[serde(default)]
+    localized_shot_name: Option<String>,
+
+    #[serde(default)]
+    shot_description: Option<String>,
+
+    #[serde(default)]
+    shot_coaching_tip: Option<String>,
+
+    #[serde(default)]
+    recommendations: Option<Vec<Recommendation>>,
+
+    #[serde(default)]
+    outcome_name: Option<String>,
+
+    #[serde(default)]
+    outcome_rank: Option<String>,
+
+    #[serde(default)]
+    combined_rank: Option<String>,
+
+    #[serde(default)]
+    distance_to_target_meters: Option<f64>,
+
+    // Outcome target
+    #[serde(default)]
+    target_distance_meters: Option<f64>,
+
+    #[serde(default)]
+    target_tolerance_meters: Option<f64>,
+
+    #[serde(default)]
+    outcome_weight: Option<f64>,
+
+    // Golfer settings
+    #[serde(default)]
+    handedness: Option<Handedness>,
+
+    #[serde(default)]
+    mirror_outputs: Option<bool>,
+
+    #[serde(default, alias = "aim_offset_degrees")]
+    target_bearing_degrees: Option<f64>,
+
+    #[serde(default)]
+    club: Option<String>,
+
+    // Classification settings
+    #[serde(default)]
+    skill_level: Option<SkillLevel>,
+
+    #[serde(default)]
+    classifier_config: Option<ClassifierConfig>,
+
+    #[serde(default)]
+    shot_classifier: Option<ShotClassifierKind>,
+
+    #[serde(default)]
+    rank_scheme: Option<String>,
+
+    #[serde(default)]
+    color_theme: Option<String>,
+
+    #[serde(default)]
+    previous_shot_name: Option<String>,
+
+    #[serde(default)]
+    locale: Option<String>,
+
     // Environmental conditions
     #[serde(default)]
     pressure_pascals: Option<f64>,
@@ -456,8 +753,293 @@ pub struct InputData {
     us_customary_units: Option<InputUSCustomaryUnits>,
 }
 
+/// Ball speed given in the input, in any of its units
+fn input_ball_speed(input: &InputData) -> Option<f64> {
+    input.ball_speed_meters_per_second.or_else(|| {
+        input
+            .ball_speed_mph
+            .or_else(|| {
+                input
+                    .us_customary_units
+                    .as_ref()
+                    .and_then(|units| units.ball_speed_mph)
+            })
+            .map(mph_to_meters_per_second)
+    })
+}
+
+/// Club speed given in the input, in any of its units
+fn input_club_speed(input: &InputData) -> Option<f64> {
+    input.club_speed_meters_per_second.or_else(|| {
+        input
+            .club_speed_mph
+            .or_else(|| {
+                input
+                    .us_customary_units
+                    .as_ref()
+                    .and_then(|units| units.club_speed_mph)
+            })
+            .map(mph_to_meters_per_second)
+    })
+}
+
+/// Club delivery described by the input when no ball launch data is present.
+///
+/// Requires club speed and dynamic loft; path, face, attack angle and impact
+/// offset default to zero.
+fn club_delivery_from_input(input: &InputData) -> Option<ClubDelivery> {
+    if input_ball_speed(input).is_some() || input.vertical_launch_angle_degrees.is_some() {
+        return None;
+    }
+
+    let club_speed = input_club_speed(input)?;
+
+    Some(ClubDelivery {
+        club_speed_meters_per_second: club_speed,
+        club_path_degrees: input.club_path_degrees.unwrap_or(0.0),
+        club_face_to_target_degrees: input.club_face_to_target_degrees.unwrap_or(0.0),
+        attack_angle_degrees: input.attack_angle_degrees.unwrap_or(0.0),
+        dynamic_loft_degrees: input.dynamic_loft_degrees?,
+        impact_offset_horizontal_millimeters: input
+            .impact_offset_horizontal_millimeters
+            .unwrap_or(0.0),
+        impact_offset_vertical_millimeters: input.impact_offset_vertical_millimeters.unwrap_or(0.0),
+    })
+}
+
+/// Fill the shot fields from a reference-library match.
+///
+/// Labels that are shot IDs take their name and rank from the shot; custom labels use the
+/// reference shot's own name and rank, and get no `shot_id`.
+fn apply_reference_match(
+    derived: &mut DerivedValues,
+    reference_match: ReferenceMatch,
+    options: &ClassificationOptions,
+) {
+    let shot_id = ShotId::from_label(&reference_match.label);
+    let name = reference_match
+        .name
+        .clone()
+        .or_else(|| shot_id.map(|id| id.english_name().to_string()))
+        .unwrap_or_else(|| reference_match.label.clone());
+    let rank = reference_match.rank.clone().or_else(|| {
+        shot_id
+            .and_then(ShotId::parts)
+            .map(|(direction, shape)| options.rank_scheme.rank(direction, shape).to_string())
+    });
+
+    if derived.shot_id.is_none() {
+        derived.shot_id = shot_id;
+    }
+    if derived.shot_name.is_none() {
+        derived.shot_name = Some(name);
+    }
+    if derived.shot_color_rgb.is_none() {
+        derived.shot_color_rgb = rank
+            .as_deref()
+            .map(|rank| options.color_theme.color_for(rank));
+    }
+    if derived.shot_rank.is_none() {
+        derived.shot_rank = rank;
+    }
+    if derived.shot_confidence.is_none() {
+        derived.shot_confidence = Some(reference_match.confidence);
+    }
+    if derived.reference_match.is_none() {
+        derived.reference_match = Some(reference_match);
+    }
+}
+
+impl InputData {
+    /// Check per-request settings that deserialize fine but cannot be used
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if let Some(speed) = input_ball_speed(self) {
+            if !(speed.is_finite() && speed > 0.0) {
+                return Err(format!("ball speed must be positive, got {}", speed));
+            }
+        }
+        if let Some(config) = &self.classifier_config {
+            if self.skill_level.is_some() {
+                return Err("give either skill_level or classifier_config, not both".to_string());
+            }
+            config.validate().map_err(|e| e.to_string())?;
+        }
+        if let Some(name) = self.rank_scheme.as_deref() {
+            if rank_scheme(name).is_none() {
+                return Err(format!(
+                    "unknown rank_scheme '{}' (expected one of: {})",
+                    name,
+                    rank_scheme_names().join(", ")
+                ));
+            }
+        }
+        if let Some(name) = self.color_theme.as_deref() {
+            if color_theme(name).is_none() {
+                return Err(format!(
+                    "unknown color_theme '{}' (expected one of: {})",
+                    name,
+                    color_theme_names().join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Thresholds, rank scheme and colors requested by the input.
+///
+/// Expects an input that passed [`InputData::validate`]; settings it leaves out come from the
+/// process-wide defaults.
+fn classification_options(input: &InputData) -> ClassificationOptions {
+    let config = input
+        .classifier_config
+        .or_else(|| input.skill_level.map(ClassifierConfig::for_skill_level))
+        .unwrap_or_else(classifier_config);
+    ClassificationOptions {
+        config,
+        rank_scheme: input
+            .rank_scheme
+            .as_deref()
+            .and_then(rank_scheme)
+            .unwrap_or_else(default_rank_scheme),
+        color_theme: input
+            .color_theme
+            .as_deref()
+            .and_then(color_theme)
+            .unwrap_or_else(default_color_theme),
+        previous_shot_name: input.previous_shot_name.clone(),
+    }
+}
+
 /// Calculate all derived values from input data
+///
+/// Left-handed input is mirrored into the right-handed frame before any calculation, so
+/// shot names and club delivery come out golfer-relative. World-frame lateral outputs
+/// (spin axis, sidespin, offline, landing vectors) are mirrored back unless
+/// `mirror_outputs` is set.
+///
+/// With a `target_bearing_degrees`, direction, club delivery, offline distance and rank are
+/// judged against that target line; ball flight and landing vectors stay on the 0° line.
 pub fn calculate_derived_values_from_input(input: &InputData) -> DerivedValues {
+    let left_handed = input.handedness.unwrap_or_default() == Handedness::Left;
+    let mut derived = if left_handed {
+        let mut derived = calculate_right_handed(&input.mirrored());
+        derived.mirror_lateral();
+        derived
+    } else {
+        calculate_right_handed(input)
+    };
+
+    // Outcome labels describe where the ball finished, so they use world-frame left/right
+    apply_outcome_classification(input, &mut derived);
+    apply_localization(input, &mut derived);
+    apply_recommendations(input, &mut derived);
+
+    if left_handed && input.mirror_outputs.unwrap_or(false) {
+        derived.mirror_lateral();
+    }
+    derived
+}
+
+/// Add the shot's localized name, description and coaching tip when a locale was requested
+fn apply_localization(input: &InputData, derived: &mut DerivedValues) {
+    let (Some(locale), Some(shot_id)) = (input.locale.as_deref(), derived.shot_id) else {
+        return;
+    };
+
+    let shot = describe_shot(shot_id, locale);
+    if derived.localized_shot_name.is_none() {
+        derived.localized_shot_name = Some(shot.name);
+    }
+    if derived.shot_description.is_none() && !shot.description.is_empty() {
+        derived.shot_description = Some(shot.description);
+    }
+    if derived.shot_coaching_tip.is_none() && !shot.coaching_tip.is_empty() {
+        derived.shot_coaching_tip = Some(shot.coaching_tip);
+    }
+}
+
+/// Add prioritized coaching recommendations, in the requested locale or English
+fn apply_recommendations(input: &InputData, derived: &mut DerivedValues) {
+    if derived.recommendations.is_none() {
+        let locale = input.locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+        let club_speed_measured = input_club_speed(input).is_some();
+        derived.recommendations = coaching_recommendations(derived, locale, club_speed_measured);
+    }
+}
+
+/// Classify the finishing position against the target distance, if one was given
+fn apply_outcome_classification(input: &InputData, derived: &mut DerivedValues) {
+    let needs_outcome = derived.outcome_name.is_none()
+        || derived.outcome_rank.is_none()
+        || derived.combined_rank.is_none()
+        || derived.distance_to_target_meters.is_none();
+    if !needs_outcome {
+        return;
+    }
+
+    let (Some(target_distance), Some(total_distance)) =
+        (input.target_distance_meters, derived.total_distance_meters)
+    else {
+        return;
+    };
+
+    // Resting position = landing position plus roll; without landing vectors, place the
+    // ball from the total and offline distances along the target line instead
+    let (resting_position, bearing) = match (derived.landing_position, derived.landing_velocity) {
+        (Some(landing_position), Some(landing_velocity)) => (
+            get_resting_position(&landing_position, &landing_velocity, total_distance),
+            input.target_bearing_degrees.unwrap_or(0.0),
+        ),
+        _ => {
+            let Some(offline) = derived.offline_distance_meters else {
+                return;
+            };
+            let along_line = (total_distance.powi(2) - offline.powi(2)).max(0.0).sqrt();
+            (Vector3::new(along_line, offline, 0.0), 0.0)
+        }
+    };
+
+    let tolerance = input
+        .target_tolerance_meters
+        .unwrap_or_else(|| default_target_tolerance(target_distance));
+    let outcome = classify_outcome(&resting_position, bearing, target_distance, tolerance);
+
+    if derived.combined_rank.is_none() {
+        let shape_rank = derived.shot_rank.as_deref().unwrap_or_default();
+        let weight = input.outcome_weight.unwrap_or(DEFAULT_OUTCOME_WEIGHT);
+        derived.combined_rank = Some(blend_ranks(shape_rank, &outcome.outcome_rank, weight));
+    }
+    if derived.outcome_name.is_none() {
+        derived.outcome_name = Some(outcome.outcome_name);
+    }
+    if derived.outcome_rank.is_none() {
+        derived.outcome_rank = Some(outcome.outcome_rank);
+    }
+    if derived.distance_to_target_meters.is_none() {
+        derived.distance_to_target_meters = Some(outcome.distance_to_target_meters);
+    }
+}
+
+/// Calculate all derived values with input in the right-handed frame
+fn calculate_right_handed(input: &InputData) -> DerivedValues {
+    // Swing simulator: predict ball launch from club delivery, then derive as usual
+    if let Some(delivery) = club_delivery_from_input(input) {
+        // Club delivery is relative to the target line; the ball flies in the world frame
+        let mut launch = simulate_impact(&delivery);
+        launch.horizontal_launch_angle_degrees += input.target_bearing_degrees.unwrap_or(0.0);
+        let mut simulated = input.clone();
+        simulated.ball_speed_meters_per_second = Some(launch.ball_speed_meters_per_second);
+        simulated.vertical_launch_angle_degrees = Some(launch.vertical_launch_angle_degrees);
+        simulated.horizontal_launch_angle_degrees = Some(launch.horizontal_launch_angle_degrees);
+        simulated.total_spin_rpm = Some(launch.total_spin_rpm);
+        simulated.spin_axis_degrees = Some(launch.spin_axis_degrees);
+
+        let mut derived = calculate_right_handed(&simulated);
+        derived.predicted_ball_launch = Some(launch);
+        return derived;
+    }
+
     let mut derived = DerivedValues::new();
 
     macro_rules! copy_if_provided {
@@ -468,6 +1050,9 @@ pub fn calculate_derived_values_from_input(input: &InputData) -> DerivedValues {
         };
     }
 
+    copy_if_provided!(club);
+    copy_if_provided!(club_loft_degrees);
+    copy_if_provided!(target_distance_meters);
     copy_if_provided!(backspin_rpm);
     copy_if_provided!(sidespin_rpm);
     copy_if_provided!(total_spin_rpm);
@@ -485,9 +1070,29 @@ pub fn calculate_derived_values_from_input(input: &InputData) -> DerivedValues {
     copy_if_provided!(club_path_degrees);
     copy_if_provided!(club_face_to_target_degrees);
     copy_if_provided!(club_face_to_path_degrees);
+    copy_if_provided!(attack_angle_degrees);
+    copy_if_provided!(dynamic_loft_degrees);
+    copy_if_provided!(spin_loft_degrees);
+    copy_if_provided!(impact_location_estimate);
+    copy_if_provided!(contact_quality);
+    copy_if_provided!(shot_id);
     copy_if_provided!(shot_name);
     copy_if_provided!(shot_rank);
     copy_if_provided!(shot_color_rgb);
+    copy_if_provided!(shot_confidence);
+    copy_if_provided!(shot_alternatives);
+    copy_if_provided!(reference_match);
+    copy_if_provided!(localized_shot_name);
+    copy_if_provided!(shot_description);
+    copy_if_provided!(shot_coaching_tip);
+    copy_if_provided!(recommendations);
+    copy_if_provided!(outcome_name);
+    copy_if_provided!(outcome_rank);
+    copy_if_provided!(combined_rank);
+    copy_if_provided!(distance_to_target_meters);
+    if derived.shot_id.is_none() {
+        derived.shot_id = derived.shot_name.as_deref().and_then(ShotId::from_label);
+    }
 
     let root_us_units = InputUSCustomaryUnits {
         ball_speed_mph: input.ball_speed_mph,
@@ -507,19 +1112,9 @@ pub fn calculate_derived_values_from_input(input: &InputData) -> DerivedValues {
         apply_us_unit_inputs(&mut derived, us_units);
     }
 
-    let mut ball_speed_mps = input.ball_speed_meters_per_second;
-    if ball_speed_mps.is_none() {
-        if let Some(speed_mph) = input.ball_speed_mph {
-            ball_speed_mps = Some(mph_to_meters_per_second(speed_mph));
-        }
-    }
-    if ball_speed_mps.is_none() {
-        if let Some(us_units) = &input.us_customary_units {
-            if let Some(speed_mph) = us_units.ball_speed_mph {
-                ball_speed_mps = Some(mph_to_meters_per_second(speed_mph));
-            }
-        }
-    }
+    let ball_speed_mps = input_ball_speed(input);
+    derived.ball_speed_meters_per_second = ball_speed_mps;
+    derived.vertical_launch_angle_degrees = input.vertical_launch_angle_degrees;
 
     // Calculate spin components if we have total spin and spin axis, but not if already provided
     if derived.backspin_rpm.is_none() || derived.sidespin_rpm.is_none() {
@@ -553,6 +1148,10 @@ pub fn calculate_derived_values_from_input(input: &InputData) -> DerivedValues {
     {
         let h_angle = input.horizontal_launch_angle_degrees.unwrap_or(0.0);
 
+        // Direction relative to the intended target line (ball flight stays on the 0° line)
+        let target_bearing = input.target_bearing_degrees.unwrap_or(0.0);
+        let target_h_angle = h_angle - target_bearing;
+
         // Use provided or derived spin values
         let backspin = input.backspin_rpm.or(derived.backspin_rpm).unwrap_or(0.0);
         let sidespin = input.sidespin_rpm.or(derived.sidespin_rpm).unwrap_or(0.0);
@@ -603,7 +1202,10 @@ pub fn calculate_derived_values_from_input(input: &InputData) -> DerivedValues {
                 derived.total_distance_meters = Some(get_total_distance(&trajectory));
             }
             if derived.offline_distance_meters.is_none() {
-                derived.offline_distance_meters = Some(get_offline_distance(&trajectory));
+                derived.offline_distance_meters = Some(get_offline_distance_from_target_line(
+                    &trajectory,
+                    target_bearing,
+                ));
             }
             if derived.descent_angle_degrees.is_none() {
                 derived.descent_angle_degrees = Some(get_descent_angle(&trajectory));
@@ -624,6 +1226,7 @@ pub fn calculate_derived_values_from_input(input: &InputData) -> DerivedValues {
         }
 
         // Determine clubhead speed: use provided value (metric or converted) if available
+        let club_speed_measured = derived.club_speed_meters_per_second.is_some();
         let club_speed = if let Some(measured_speed) = derived.club_speed_meters_per_second {
             measured_speed
         } else {
@@ -661,15 +1264,85 @@ pub fn calculate_derived_values_from_input(input: &InputData) -> DerivedValues {
             derived.distance_efficiency_percent = Some(efficiency.round());
         }
 
+        // Total spin for club delivery estimates (provided, derived, or from components)
+        let total_spin_for_delivery =
+            input.total_spin_rpm.or(derived.total_spin_rpm).or_else(|| {
+                derived
+                    .backspin_rpm
+                    .zip(derived.sidespin_rpm)
+                    .map(|(bs, ss)| (bs.powi(2) + ss.powi(2)).sqrt())
+            });
+
+        // Impact location: use the strike offset if provided, otherwise infer it for woods
+        if derived.impact_location_estimate.is_none() {
+            let provided_offset = input
+                .impact_offset_horizontal_millimeters
+                .or(input.impact_offset_vertical_millimeters)
+                .map(|_| ImpactLocationEstimate {
+                    horizontal_millimeters: input
+                        .impact_offset_horizontal_millimeters
+                        .unwrap_or(0.0),
+                    vertical_millimeters: input.impact_offset_vertical_millimeters.unwrap_or(0.0),
+                    confidence: 1.0,
+                });
+
+            derived.impact_location_estimate = provided_offset.or_else(|| {
+                let spin_axis = input.spin_axis_degrees.or(derived.spin_axis_degrees)?;
+                let total_spin = total_spin_for_delivery?;
+                if !club_speed_measured || input.horizontal_launch_angle_degrees.is_none() {
+                    return None;
+                }
+                estimate_impact_location(
+                    ball_speed,
+                    club_speed,
+                    v_angle,
+                    target_h_angle,
+                    total_spin,
+                    spin_axis,
+                )
+            });
+        }
+
         // Estimate club face/path relationship when we have horizontal launch data, but only if not provided
         let needs_face_path = derived.club_path_degrees.is_none()
             || derived.club_face_to_target_degrees.is_none()
             || derived.club_face_to_path_degrees.is_none();
 
         if needs_face_path {
-            if let Some(spin_axis) = input.spin_axis_degrees.or(derived.spin_axis_degrees) {
+            if let Some(measured_axis) = input.spin_axis_degrees.or(derived.spin_axis_degrees) {
                 if input.horizontal_launch_angle_degrees.is_some() {
-                    let estimates = estimate_club_face_path(ball_speed, h_angle, spin_axis);
+                    // Strip gear effect so only face-to-path curvature feeds the D-plane,
+                    // trusting inferred locations only as far as their confidence.
+                    let spin_axis = match derived.impact_location_estimate {
+                        Some(location) => {
+                            measured_axis
+                                - gear_effect_spin_axis_degrees(
+                                    ball_speed,
+                                    location.horizontal_millimeters * location.confidence,
+                                )
+                        }
+                        None => measured_axis,
+                    };
+
+                    // Prefer the 3D D-plane when spin rate is known; otherwise fall back
+                    // to the horizontal-only estimate.
+                    let estimates = match total_spin_for_delivery {
+                        Some(total_spin) => {
+                            let delivery = estimate_d_plane_delivery(
+                                ball_speed,
+                                target_h_angle,
+                                v_angle,
+                                total_spin,
+                                spin_axis,
+                            );
+                            ClubFacePathEstimates {
+                                club_path_degrees: delivery.club_path_degrees,
+                                club_face_to_target_degrees: delivery.club_face_to_target_degrees,
+                                club_face_to_path_degrees: delivery.club_face_to_path_degrees,
+                            }
+                        }
+                        None => estimate_club_face_path(ball_speed, target_h_angle, spin_axis),
+                    };
                     if derived.club_path_degrees.is_none() {
                         derived.club_path_degrees = Some(estimates.club_path_degrees);
                     }
@@ -685,8 +1358,47 @@ pub fn calculate_derived_values_from_input(input: &InputData) -> DerivedValues {
             }
         }
 
-        // Shot classification (vector similarity) if not already provided
-        let needs_shot_classification = derived.shot_name.is_none()
+        // Contact quality: compare launch and spin with the band's optimal window
+        if derived.contact_quality.is_none() {
+            if let Some(total_spin) = total_spin_for_delivery {
+                derived.contact_quality = detect_contact_quality(
+                    ball_speed,
+                    club_speed_measured.then_some(club_speed),
+                    v_angle,
+                    total_spin,
+                    derived.impact_location_estimate.as_ref(),
+                );
+            }
+        }
+
+        // Estimate vertical delivery (attack angle, dynamic loft, spin loft) if not provided
+        let needs_loft_attack = derived.attack_angle_degrees.is_none()
+            || derived.dynamic_loft_degrees.is_none()
+            || derived.spin_loft_degrees.is_none();
+
+        if needs_loft_attack {
+            if let Some(total_spin) = total_spin_for_delivery {
+                let estimates = estimate_club_loft_attack(
+                    ball_speed,
+                    v_angle,
+                    total_spin,
+                    input.club_loft_degrees,
+                );
+                if derived.attack_angle_degrees.is_none() {
+                    derived.attack_angle_degrees = Some(estimates.attack_angle_degrees);
+                }
+                if derived.dynamic_loft_degrees.is_none() {
+                    derived.dynamic_loft_degrees = Some(estimates.dynamic_loft_degrees);
+                }
+                if derived.spin_loft_degrees.is_none() {
+                    derived.spin_loft_degrees = Some(estimates.spin_loft_degrees);
+                }
+            }
+        }
+
+        // Shot classification (threshold rules, or nearest reference shots) if not already provided
+        let needs_shot_classification = derived.shot_id.is_none()
+            || derived.shot_name.is_none()
             || derived.shot_rank.is_none()
             || derived.shot_color_rgb.is_none();
 
@@ -706,9 +1418,41 @@ pub fn calculate_derived_values_from_input(input: &InputData) -> DerivedValues {
                 total_spin_for_classification,
                 spin_axis_for_classification,
             ) {
-                if let Some(classification) =
-                    classify_shot(ball_speed, v_angle, h_angle, total_spin, spin_axis)
-                {
+                let options = classification_options(input);
+                let reference_match = (input.shot_classifier.unwrap_or_default()
+                    == ShotClassifierKind::NearestNeighbor)
+                    .then(|| {
+                        // Curve: how far the landing spot is from where the start line points
+                        let curve = derived
+                            .offline_distance_meters
+                            .zip(derived.carry_distance_meters)
+                            .map(|(offline, carry)| {
+                                offline - carry * (target_h_angle * PI / 180.0).sin()
+                            });
+                        classify_with_reference_library(&ShotFeatures {
+                            ball_speed_meters_per_second: Some(ball_speed),
+                            vertical_launch_angle_degrees: Some(v_angle),
+                            horizontal_launch_angle_degrees: Some(target_h_angle),
+                            spin_axis_degrees: Some(spin_axis),
+                            curve_meters: curve,
+                            offline_meters: derived.offline_distance_meters,
+                        })
+                    })
+                    .flatten();
+
+                if let Some(reference_match) = reference_match {
+                    apply_reference_match(&mut derived, reference_match, &options);
+                } else if let Some(classification) = classify_shot(
+                    ball_speed,
+                    v_angle,
+                    target_h_angle,
+                    total_spin,
+                    spin_axis,
+                    &options,
+                ) {
+                    if derived.shot_id.is_none() {
+                        derived.shot_id = Some(classification.shot_id);
+                    }
                     if derived.shot_name.is_none() {
                         derived.shot_name = Some(classification.shot_name);
                     }
@@ -718,6 +1462,12 @@ pub fn calculate_derived_values_from_input(input: &InputData) -> DerivedValues {
                     if derived.shot_color_rgb.is_none() {
                         derived.shot_color_rgb = Some(classification.shot_color_rgb);
                     }
+                    if derived.shot_confidence.is_none() {
+                        derived.shot_confidence = Some(classification.confidence);
+                    }
+                    if derived.shot_alternatives.is_none() {
+                        derived.shot_alternatives = Some(classification.alternatives);
+                    }
                 }
             }
         }
@@ -789,6 +1539,23 @@ mod tests {
     use super::*;
     use unit_conversions::{mph_to_meters_per_second, yards_to_meters};
 
+    /// Derived values for a 65 m/s shot; `extra` is JSON fields added to or overriding it
+    fn derive_with(extra: &str) -> Value {
+        let mut input = serde_json::json!({
+            "ball_speed_meters_per_second": 65.0,
+            "vertical_launch_angle_degrees": 12.0,
+            "horizontal_launch_angle_degrees": 0.0,
+            "total_spin_rpm": 3000.0,
+            "spin_axis_degrees": 0.0
+        });
+        let fields: serde_json::Map<String, Value> =
+            serde_json::from_str(&format!("{{{}}}", extra)).unwrap();
+        input.as_object_mut().unwrap().extend(fields);
+        let result = calculate_derived_values(&input.to_string()).unwrap();
+        let output: Value = serde_json::from_str(&result).unwrap();
+        output["open_golf_coach"].clone()
+    }
+
     #[test]
     fn test_spin_components() {
         let (backspin, sidespin) = calculate_spin_components(3000.0, 0.0);
@@ -953,6 +1720,9 @@ mod tests {
         assert!(derived.get("club_path_degrees").is_some());
         assert!(derived.get("club_face_to_target_degrees").is_some());
         assert!(derived.get("club_face_to_path_degrees").is_some());
+        assert!(derived.get("attack_angle_degrees").is_some());
+        assert!(derived.get("dynamic_loft_degrees").is_some());
+        assert!(derived.get("spin_loft_degrees").is_some());
         assert!(derived.get("shot_name").is_some());
         assert!(derived.get("shot_rank").is_some());
         assert!(derived.get("shot_color_rgb").is_some());
@@ -1050,6 +1820,376 @@ mod tests {
         assert_eq!(derived["shot_color_rgb"], "0x7CB342");
     }
 
+    #[test]
+    fn test_skill_level_changes_classification_thresholds() {
+        let classify = |extra: &str| {
+            let json_input = format!(
+                r#"{{
+                    "ball_speed_meters_per_second": 65.0,
+                    "vertical_launch_angle_degrees": 12.0,
+                    "horizontal_launch_angle_degrees": 4.0,
+                    "total_spin_rpm": 2800.0,
+                    "spin_axis_degrees": 0.0{}
+                }}"#,
+                extra
+            );
+            let result = calculate_derived_values(&json_input).unwrap();
+            let output: Value = serde_json::from_str(&result).unwrap();
+            output["open_golf_coach"]["shot_name"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(classify(""), "Push");
+        assert_eq!(classify(r#", "skill_level": "beginner""#), "Straight");
+        assert_eq!(
+            classify(r#", "classifier_config": {"straight_max_hla_degrees": 5.0}"#),
+            "Straight"
+        );
+    }
+
+    #[test]
+    fn test_invalid_classifier_config_is_rejected() {
+        // calculate_derived_values reports this as "Invalid input format: ..."
+        let input = serde_json::json!({
+            "ball_speed_meters_per_second": 65.0,
+            "vertical_launch_angle_degrees": 12.0,
+            "horizontal_launch_angle_degrees": 0.0,
+            "classifier_config": {"straight_max_hla_degrees": -5.0}
+        });
+        let error = bindings::prepare_input_data(&input).unwrap_err();
+        assert!(
+            error.to_string().contains("Invalid classifier config"),
+            "{}",
+            error
+        );
+
+        let both = serde_json::json!({
+            "ball_speed_meters_per_second": 65.0,
+            "skill_level": "beginner",
+            "classifier_config": {"straight_max_hla_degrees": 5.0}
+        });
+        let error = bindings::prepare_input_data(&both).unwrap_err();
+        assert!(error.to_string().contains("not both"), "{}", error);
+    }
+
+    #[test]
+    fn test_near_boundary_confidence_and_hysteresis() {
+        let classify = |hla: f64, extra: &str| {
+            let json_input = format!(
+                r#"{{
+                    "ball_speed_meters_per_second": 65.0,
+                    "vertical_launch_angle_degrees": 12.0,
+                    "horizontal_launch_angle_degrees": {},
+                    "total_spin_rpm": 2800.0,
+                    "spin_axis_degrees": 0.0{}
+                }}"#,
+                hla, extra
+            );
+            let result = calculate_derived_values(&json_input).unwrap();
+            let output: Value = serde_json::from_str(&result).unwrap();
+            output["open_golf_coach"].clone()
+        };
+
+        let edge = classify(3.1, "");
+        assert_eq!(edge["shot_name"], "Push");
+        assert!(edge["shot_confidence"].as_f64().unwrap() < 0.6);
+        assert_eq!(edge["shot_alternatives"][0]["shot_name"], "Straight");
+        assert!(
+            (edge["shot_alternatives"][0]["boundary_distance_degrees"]
+                .as_f64()
+                .unwrap()
+                - 0.1)
+                .abs()
+                < 1e-9
+        );
+
+        let clear = classify(8.0, "");
+        assert_eq!(clear["shot_confidence"].as_f64().unwrap(), 1.0);
+
+        // The previous shot was straight, so a shot just past the boundary keeps that label
+        let held = classify(3.1, r#", "previous_shot_name": "Straight""#);
+        assert_eq!(held["shot_name"], "Straight");
+        let released = classify(4.0, r#", "previous_shot_name": "Straight""#);
+        assert_eq!(released["shot_name"], "Push");
+    }
+
+    #[test]
+    fn test_shot_id_in_output() {
+        let json_input = r#"{
+            "ball_speed_meters_per_second": 65.0,
+            "vertical_launch_angle_degrees": 12.0,
+            "horizontal_launch_angle_degrees": 4.0,
+            "total_spin_rpm": 3000.0,
+            "spin_axis_degrees": -6.0
+        }"#;
+        let result = calculate_derived_values(json_input).unwrap();
+        let output: Value = serde_json::from_str(&result).unwrap();
+        let derived = &output["open_golf_coach"];
+
+        assert_eq!(derived["shot_id"], "push_draw");
+        assert_eq!(derived["shot_name"], "Push Draw");
+        assert!(derived["shot_alternatives"][0]["shot_id"].is_string());
+
+        // A provided English name keeps its ID without reclassifying
+        let provided = calculate_derived_values(
+            r#"{"ball_speed_meters_per_second": 65.0, "shot_name": "Pull Fade", "shot_rank": "B", "shot_color_rgb": "0x7CB342"}"#,
+        )
+        .unwrap();
+        let provided: Value = serde_json::from_str(&provided).unwrap();
+        assert_eq!(provided["open_golf_coach"]["shot_id"], "pull_fade");
+    }
+
+    #[test]
+    fn test_session_from_club_tagged_shots() {
+        let mut session = Session::new();
+        for (club, speed_mph, launch) in [
+            ("7i", 120.0, 16.0),
+            ("7i", 124.0, 17.0),
+            ("Driver", 160.0, 12.0),
+        ] {
+            let input: InputData = serde_json::from_value(serde_json::json!({
+                "club": club,
+                "ball_speed_mph": speed_mph,
+                "vertical_launch_angle_degrees": launch,
+                "total_spin_rpm": 5000.0,
+                "spin_axis_degrees": 0.0
+            }))
+            .unwrap();
+            session.add(calculate_derived_values_from_input(&input));
+        }
+
+        let iron = session.shots()[0].clone();
+        assert_eq!(iron.club.as_deref(), Some("7i"));
+        assert!(
+            (iron.ball_speed_meters_per_second.unwrap() - mph_to_meters_per_second(120.0)).abs()
+                < 1e-9
+        );
+
+        let summary = session.summary();
+        assert_eq!(summary.clubs.len(), 2);
+        let carry = &summary.clubs[0].metrics[&SessionMetric::CarryDistanceMeters];
+        assert_eq!(carry.count, 2);
+        assert!(carry.mean < summary.clubs[1].metrics[&SessionMetric::CarryDistanceMeters].mean);
+        assert_eq!(
+            summary.clubs[0].metrics[&SessionMetric::VerticalLaunchAngleDegrees].mean,
+            16.5
+        );
+    }
+
+    #[test]
+    fn test_locale_adds_shot_text() {
+        let derive = |extra: &str| {
+            derive_with(&format!(
+                r#""horizontal_launch_angle_degrees": 4.0, "spin_axis_degrees": -6.0{}"#,
+                extra
+            ))
+        };
+
+        assert!(derive("").get("shot_description").is_none());
+
+        // No French bundle ships, so the text falls back to English
+        let localized = derive(r#", "locale": "fr-CA""#);
+        assert_eq!(localized["localized_shot_name"], "Push Draw");
+        assert!(localized["shot_description"]
+            .as_str()
+            .unwrap()
+            .contains("push draw"));
+        assert!(localized["shot_coaching_tip"].is_string());
+    }
+
+    #[test]
+    fn test_recommendations_in_output() {
+        let json_input = r#"{
+            "ball_speed_meters_per_second": 65.0,
+            "club_speed_meters_per_second": 52.0,
+            "vertical_launch_angle_degrees": 12.0,
+            "horizontal_launch_angle_degrees": 0.0,
+            "total_spin_rpm": 3000.0,
+            "spin_axis_degrees": 4.0,
+            "club": "Driver"
+        }"#;
+        let result = calculate_derived_values(json_input).unwrap();
+        let output: Value = serde_json::from_str(&result).unwrap();
+        let recommendations = output["open_golf_coach"]["recommendations"]
+            .as_array()
+            .unwrap();
+
+        let ids: Vec<_> = recommendations
+            .iter()
+            .map(|r| r["id"].as_str().unwrap())
+            .collect();
+        assert!(ids.contains(&"center_contact"), "{:?}", ids);
+        assert!(ids.contains(&"hit_up_with_driver"), "{:?}", ids);
+        assert_eq!(recommendations[0]["priority"], 1);
+        let smash = recommendations
+            .iter()
+            .find(|r| r["id"] == "center_contact")
+            .unwrap();
+        assert_eq!(smash["metric"], "smash_factor");
+        assert!((smash["value"].as_f64().unwrap() - 1.25).abs() < 1e-9);
+        assert!(smash["title"].as_str().unwrap().contains("1.25"));
+    }
+
+    #[test]
+    fn test_rank_scheme_and_color_theme_per_request() {
+        let derive = |extra: &str| {
+            derive_with(&format!(
+                r#""horizontal_launch_angle_degrees": 5.0, "spin_axis_degrees": 6.0{}"#,
+                extra
+            ))
+        };
+
+        let default = derive("");
+        assert_eq!(default["shot_name"], "Push Fade");
+        assert_eq!(default["shot_rank"], "C");
+        assert_eq!(default["shot_color_rgb"], "0xFFC107");
+
+        let league = derive(r#", "rank_scheme": "fade_bias", "color_theme": "colorblind""#);
+        assert_eq!(league["shot_rank"], "B");
+        assert_eq!(league["shot_color_rgb"], "0xF0E442");
+
+        // Unknown names are rejected with the valid ones listed
+        let unknown = |extra: serde_json::Value| {
+            let mut input = serde_json::json!({ "ball_speed_meters_per_second": 65.0 });
+            input
+                .as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            bindings::prepare_input_data(&input)
+                .unwrap_err()
+                .to_string()
+        };
+        let error = unknown(serde_json::json!({ "rank_scheme": "nope" }));
+        assert!(error.contains("unknown rank_scheme 'nope'"), "{}", error);
+        assert!(error.contains("fade_bias"), "{}", error);
+        let error = unknown(serde_json::json!({ "color_theme": "nope" }));
+        assert!(error.contains("unknown color_theme 'nope'"), "{}", error);
+        assert!(error.contains("colorblind"), "{}", error);
+    }
+
+    #[test]
+    fn test_nearest_neighbor_classifier() {
+        let derive = |extra: &str| {
+            derive_with(&format!(
+                r#""horizontal_launch_angle_degrees": 5.5, "spin_axis_degrees": -8.0{}"#,
+                extra
+            ))
+        };
+
+        assert!(derive("").get("reference_match").is_none());
+
+        let matched = derive(r#", "shot_classifier": "nearest_neighbor""#);
+        assert_eq!(matched["reference_match"]["label"], "push_draw");
+        assert_eq!(matched["shot_id"], "push_draw");
+        assert_eq!(matched["shot_name"], "Push Draw");
+        assert_eq!(matched["shot_rank"], "A");
+    }
+
+    #[test]
+    fn test_left_handed_shot_is_golfer_relative() {
+        let derive = |extra: &str| {
+            derive_with(&format!(
+                r#""horizontal_launch_angle_degrees": -4.0, "spin_axis_degrees": -8.0{}"#,
+                extra
+            ))
+        };
+
+        let righty = derive("");
+        let lefty = derive(r#", "handedness": "left""#);
+        let mirrored = derive(r#", "handedness": "left", "mirror_outputs": true"#);
+
+        assert_eq!(righty["shot_name"], "Pull Draw");
+        assert_eq!(lefty["shot_name"], "Push Fade");
+
+        // Club delivery is golfer-relative: a lefty's in-to-out path points left
+        let righty_path = righty["club_path_degrees"].as_f64().unwrap();
+        let lefty_path = lefty["club_path_degrees"].as_f64().unwrap();
+        assert!((righty_path + lefty_path).abs() < 1e-9);
+
+        // World-frame outputs stay put unless mirroring is requested
+        let offline = righty["offline_distance_meters"].as_f64().unwrap();
+        assert!(offline < 0.0);
+        assert!((lefty["offline_distance_meters"].as_f64().unwrap() - offline).abs() < 1e-9);
+        assert!((mirrored["offline_distance_meters"].as_f64().unwrap() + offline).abs() < 1e-9);
+        assert_eq!(lefty["spin_axis_degrees"], righty["spin_axis_degrees"]);
+    }
+
+    #[test]
+    fn test_target_bearing_judges_direction_from_target_line() {
+        let derive = |extra: &str| {
+            derive_with(&format!(
+                r#""horizontal_launch_angle_degrees": 6.0, "spin_axis_degrees": 0.0{}"#,
+                extra
+            ))
+        };
+
+        let down_range = derive("");
+        let at_flag = derive(r#", "target_bearing_degrees": 6.0"#);
+
+        assert_eq!(down_range["shot_name"], "Push");
+        assert_eq!(at_flag["shot_name"], "Straight");
+        assert!(down_range["offline_distance_meters"].as_f64().unwrap() > 10.0);
+        let offline = at_flag["offline_distance_meters"].as_f64().unwrap();
+        assert!(offline.abs() < 2.0, "{}", offline);
+
+        // Aiming does not change where the ball lands
+        assert_eq!(down_range["landing_position"], at_flag["landing_position"]);
+        assert_eq!(
+            down_range["carry_distance_meters"],
+            at_flag["carry_distance_meters"]
+        );
+
+        let aliased = derive(r#", "aim_offset_degrees": 6.0"#);
+        assert_eq!(aliased["shot_name"], "Straight");
+    }
+
+    #[test]
+    fn test_outcome_classification_against_target() {
+        let derive = |extra: &str| {
+            derive_with(&format!(
+                r#""ball_speed_meters_per_second": 60.0, "vertical_launch_angle_degrees": 14.0, "horizontal_launch_angle_degrees": 4.0, "total_spin_rpm": 5000.0, "spin_axis_degrees": -14.0{}"#,
+                extra
+            ))
+        };
+
+        let plain = derive("");
+        assert_eq!(plain["shot_name"], "Push Hook");
+        assert!(plain.get("outcome_name").is_none());
+
+        // Push hook that finishes level with the flag, inside a 10 m window: outcome lifts
+        // the combined rank. The finish includes the roll, which carries the hook further left.
+        let vector = |key: &str| -> Vector3 { serde_json::from_value(plain[key].clone()).unwrap() };
+        let resting = get_resting_position(
+            &vector("landing_position"),
+            &vector("landing_velocity"),
+            plain["total_distance_meters"].as_f64().unwrap(),
+        );
+        assert!(resting.y < plain["offline_distance_meters"].as_f64().unwrap());
+        let flag = resting.x;
+        let on_flag = derive(&format!(
+            r#", "target_distance_meters": {}, "target_tolerance_meters": 10.0"#,
+            flag
+        ));
+        assert_eq!(on_flag["shot_rank"], "C");
+        assert_eq!(on_flag["outcome_name"], "On target");
+        assert_eq!(on_flag["outcome_rank"], "A");
+        assert_eq!(on_flag["combined_rank"], "B");
+
+        // A generous window (e.g. a wide fairway) counts the same finish as a bullseye
+        let wide = derive(&format!(
+            r#", "target_distance_meters": {}, "target_tolerance_meters": 40.0"#,
+            flag
+        ));
+        assert_eq!(wide["outcome_rank"], "S+");
+        assert_eq!(wide["combined_rank"], "A");
+
+        let short = derive(&format!(r#", "target_distance_meters": {}"#, flag + 40.0));
+        assert!(short["outcome_name"].as_str().unwrap().starts_with("Short"));
+        assert_eq!(short["outcome_rank"], "E");
+    }
+
     #[test]
     fn test_us_customary_input_conversion() {
         let json_input = r#"{
@@ -1084,4 +2224,106 @@ mod tests {
         assert!((us_units["carry_distance_yards"].as_f64().unwrap() - 240.0).abs() < 1e-6);
         assert!((us_units["ball_speed_mph"].as_f64().unwrap() - 150.0).abs() < 0.1);
     }
+
+    #[test]
+    fn test_club_loft_input_shifts_dynamic_loft() {
+        let base = r#"{
+            "ball_speed_meters_per_second": 53.6,
+            "vertical_launch_angle_degrees": 16.0,
+            "horizontal_launch_angle_degrees": 0.0,
+            "total_spin_rpm": 7000.0,
+            "spin_axis_degrees": 0.0
+        }"#;
+        let lofted = r#"{
+            "ball_speed_meters_per_second": 53.6,
+            "vertical_launch_angle_degrees": 16.0,
+            "horizontal_launch_angle_degrees": 0.0,
+            "total_spin_rpm": 7000.0,
+            "spin_axis_degrees": 0.0,
+            "club_loft_degrees": 34.0
+        }"#;
+
+        let base_output: Value =
+            serde_json::from_str(&calculate_derived_values(base).unwrap()).unwrap();
+        let lofted_output: Value =
+            serde_json::from_str(&calculate_derived_values(lofted).unwrap()).unwrap();
+
+        let base_loft = base_output["open_golf_coach"]["dynamic_loft_degrees"]
+            .as_f64()
+            .unwrap();
+        let lofted_loft = lofted_output["open_golf_coach"]["dynamic_loft_degrees"]
+            .as_f64()
+            .unwrap();
+        assert!(
+            lofted_loft > base_loft,
+            "Static loft {} should pull dynamic loft up from {}",
+            lofted_loft,
+            base_loft
+        );
+    }
+
+    #[test]
+    fn test_club_delivery_input_predicts_ball_flight() {
+        let json_input = r#"{
+            "club_speed_mph": 105.0,
+            "club_path_degrees": -2.0,
+            "club_face_to_target_degrees": 1.0,
+            "attack_angle_degrees": 1.5,
+            "dynamic_loft_degrees": 14.0
+        }"#;
+
+        let result = calculate_derived_values(json_input).unwrap();
+        let output: Value = serde_json::from_str(&result).unwrap();
+        let derived = &output["open_golf_coach"];
+
+        let launch = &derived["predicted_ball_launch"];
+        assert!(launch["ball_speed_meters_per_second"].as_f64().unwrap() > 60.0);
+        assert!(launch["spin_axis_degrees"].as_f64().unwrap() > 0.0);
+        assert!(derived["carry_distance_meters"].as_f64().unwrap() > 150.0);
+
+        // Provided club delivery is echoed back rather than re-estimated
+        assert!((derived["club_path_degrees"].as_f64().unwrap() + 2.0).abs() < 1e-6);
+        assert!((derived["dynamic_loft_degrees"].as_f64().unwrap() - 14.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_impact_location_input_adjusts_face_path() {
+        let centered = r#"{
+            "ball_speed_meters_per_second": 70.0,
+            "vertical_launch_angle_degrees": 12.0,
+            "horizontal_launch_angle_degrees": 1.0,
+            "total_spin_rpm": 2600.0,
+            "spin_axis_degrees": -8.0
+        }"#;
+        let toe = r#"{
+            "ball_speed_meters_per_second": 70.0,
+            "vertical_launch_angle_degrees": 12.0,
+            "horizontal_launch_angle_degrees": 1.0,
+            "total_spin_rpm": 2600.0,
+            "spin_axis_degrees": -8.0,
+            "impact_offset_horizontal_millimeters": 12.0
+        }"#;
+
+        let centered_output: Value =
+            serde_json::from_str(&calculate_derived_values(centered).unwrap()).unwrap();
+        let toe_output: Value =
+            serde_json::from_str(&calculate_derived_values(toe).unwrap()).unwrap();
+
+        let centered_f2p = centered_output["open_golf_coach"]["club_face_to_path_degrees"]
+            .as_f64()
+            .unwrap();
+        let toe_f2p = toe_output["open_golf_coach"]["club_face_to_path_degrees"]
+            .as_f64()
+            .unwrap();
+        assert!(
+            toe_f2p > centered_f2p,
+            "Toe gear effect explains hook spin, so face-to-path {} should be less closed than {}",
+            toe_f2p,
+            centered_f2p
+        );
+
+        let location = &toe_output["open_golf_coach"]["impact_location_estimate"];
+        assert!((location["horizontal_millimeters"].as_f64().unwrap() - 12.0).abs() < 1e-6);
+        assert!((location["confidence"].as_f64().unwrap() - 1.0).abs() < 1e-6);
+    }
 }
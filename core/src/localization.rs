@@ -0,0 +1,349 @@
+use crate::coaching::RecommendationId;
+use crate::config_file::{load_file, parse_toml, ConfigError};
+use crate::shot_classifier::ShotId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+static LOCALES: OnceLock<RwLock<HashMap<String, LocaleBundle>>> = OnceLock::new();
+
+/// Locale every lookup falls back to
+pub const DEFAULT_LOCALE: &str = "en";
+
+const DATA: &str = "locale";
+
+macro_rules! include_locale {
+    ($path:literal) => {
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/../locales/", $path))
+    };
+}
+
+/// Localized name and coaching text for a shot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShotDescription {
+    pub shot_id: ShotId,
+    pub name: String,
+    pub description: String,
+    pub coaching_tip: String,
+}
+
+/// Localized title and advice for a coaching recommendation; `{value}` marks where the
+/// triggering number goes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecommendationText {
+    pub id: RecommendationId,
+    pub title: String,
+    pub advice: String,
+}
+
+/// Shot entry as written in `locales/shots/shots_<locale>.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct ShotText {
+    name: Option<String>,
+    description: Option<String>,
+    coaching_tip: Option<String>,
+}
+
+/// Recommendation entry as written in `locales/recommendations/recommendations_<locale>.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct AdviceText {
+    title: Option<String>,
+    advice: Option<String>,
+}
+
+/// Any locale file: `[shots.<locale>.<shot_id>]`, `[definitions.<locale>.<category>]` and/or
+/// `[recommendations.<locale>.<recommendation_id>]`
+#[derive(Deserialize)]
+struct LocaleFile {
+    #[serde(default)]
+    shots: HashMap<String, HashMap<String, ShotText>>,
+    #[serde(default)]
+    definitions: HashMap<String, HashMap<String, HashMap<String, String>>>,
+    #[serde(default)]
+    recommendations: HashMap<String, HashMap<String, AdviceText>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct LocaleBundle {
+    shots: HashMap<String, ShotText>,
+    /// Field definitions flattened across categories
+    definitions: HashMap<String, String>,
+    recommendations: HashMap<String, AdviceText>,
+}
+
+fn normalize_locale(locale: &str) -> String {
+    locale.trim().to_lowercase().replace('_', "-")
+}
+
+fn clean(text: String) -> Option<String> {
+    let trimmed = text.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+/// Merge a parsed file into the registry, entry by entry, returning the locales it touched
+fn merge_locale_file(locales: &mut HashMap<String, LocaleBundle>, file: LocaleFile) -> Vec<String> {
+    let mut touched = Vec::new();
+    for (locale, shots) in file.shots {
+        let locale = normalize_locale(&locale);
+        let bundle = locales.entry(locale.clone()).or_default();
+        for (shot_id, text) in shots {
+            let entry = bundle.shots.entry(shot_id).or_default();
+            if let Some(name) = text.name.and_then(clean) {
+                entry.name = Some(name);
+            }
+            if let Some(description) = text.description.and_then(clean) {
+                entry.description = Some(description);
+            }
+            if let Some(tip) = text.coaching_tip.and_then(clean) {
+                entry.coaching_tip = Some(tip);
+            }
+        }
+        touched.push(locale);
+    }
+    for (locale, recommendations) in file.recommendations {
+        let locale = normalize_locale(&locale);
+        let bundle = locales.entry(locale.clone()).or_default();
+        for (id, text) in recommendations {
+            let entry = bundle.recommendations.entry(id).or_default();
+            if let Some(title) = text.title.and_then(clean) {
+                entry.title = Some(title);
+            }
+            if let Some(advice) = text.advice.and_then(clean) {
+                entry.advice = Some(advice);
+            }
+        }
+        touched.push(locale);
+    }
+    for (locale, categories) in file.definitions {
+        let locale = normalize_locale(&locale);
+        let bundle = locales.entry(locale.clone()).or_default();
+        for (field, text) in categories.into_values().flatten() {
+            if let Some(text) = clean(text) {
+                bundle.definitions.insert(field, text);
+            }
+        }
+        touched.push(locale);
+    }
+    touched.sort();
+    touched.dedup();
+    touched
+}
+
+fn parse_locale(toml_text: &str) -> Result<LocaleFile, ConfigError> {
+    parse_toml(DATA, toml_text)
+}
+
+fn embedded_locales() -> HashMap<String, LocaleBundle> {
+    let mut locales = HashMap::new();
+    for text in [
+        include_locale!("shots/shots_en.toml"),
+        include_locale!("definitions/definitions_en.toml"),
+        include_locale!("recommendations/recommendations_en.toml"),
+    ] {
+        let file = parse_locale(text).expect("embedded locale files must be valid");
+        merge_locale_file(&mut locales, file);
+    }
+    locales
+}
+
+fn locales_lock() -> &'static RwLock<HashMap<String, LocaleBundle>> {
+    LOCALES.get_or_init(|| RwLock::new(embedded_locales()))
+}
+
+/// Locales to try in order: exact tag, its language, then English
+fn fallback_chain(locale: &str) -> Vec<String> {
+    let mut chain = vec![normalize_locale(locale)];
+    if let Some((language, _)) = chain[0].split_once('-') {
+        chain.push(language.to_string());
+    }
+    chain.push(DEFAULT_LOCALE.to_string());
+    chain.dedup();
+    chain
+}
+
+/// Localized name, description and coaching tip for a shot.
+///
+/// Each text falls back separately from e.g. `pt-br` to `pt` to English; the name falls back
+/// to the English `shot_name` when no locale provides one.
+pub fn describe_shot(shot_id: ShotId, locale: &str) -> ShotDescription {
+    let locales = locales_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let chain = fallback_chain(locale);
+    let lookup = |pick: fn(&ShotText) -> Option<&String>| {
+        chain
+            .iter()
+            .filter_map(|tag| locales.get(tag)?.shots.get(shot_id.as_str()))
+            .find_map(|text| pick(text).cloned())
+    };
+
+    ShotDescription {
+        shot_id,
+        name: lookup(|text| text.name.as_ref())
+            .unwrap_or_else(|| shot_id.english_name().to_string()),
+        description: lookup(|text| text.description.as_ref()).unwrap_or_default(),
+        coaching_tip: lookup(|text| text.coaching_tip.as_ref()).unwrap_or_default(),
+    }
+}
+
+/// Localized title and advice for a coaching recommendation, each falling back separately
+/// to English
+pub fn describe_recommendation(id: RecommendationId, locale: &str) -> RecommendationText {
+    let locales = locales_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let chain = fallback_chain(locale);
+    let lookup = |pick: fn(&AdviceText) -> Option<&String>| {
+        chain
+            .iter()
+            .filter_map(|tag| locales.get(tag)?.recommendations.get(id.as_str()))
+            .find_map(|text| pick(text).cloned())
+    };
+
+    RecommendationText {
+        id,
+        title: lookup(|text| text.title.as_ref()).unwrap_or_else(|| id.as_str().to_string()),
+        advice: lookup(|text| text.advice.as_ref()).unwrap_or_default(),
+    }
+}
+
+/// Plain-language definition of an input or output field, falling back to English
+pub fn define_field(field: &str, locale: &str) -> Option<String> {
+    let locales = locales_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    fallback_chain(locale)
+        .iter()
+        .find_map(|tag| locales.get(tag)?.definitions.get(field).cloned())
+}
+
+/// Locales with at least one shot, field or recommendation entry loaded
+pub fn available_locales() -> Vec<String> {
+    let locales = locales_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut tags: Vec<String> = locales.keys().cloned().collect();
+    tags.sort();
+    tags
+}
+
+/// Merge a shots, definitions or recommendations locale file (same layout as `locales/`) into the loaded locales.
+///
+/// Entries override matching entries already loaded; everything else is kept.
+///
+/// # Returns
+/// The locale tags found in the file
+pub fn load_locale_from_str(toml_text: &str) -> Result<Vec<String>, ConfigError> {
+    let file = parse_locale(toml_text)?;
+    let mut locales = locales_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    Ok(merge_locale_file(&mut locales, file))
+}
+
+/// Load every `.toml` file in a locale directory laid out like `locales/`
+/// (`shots/shots_<locale>.toml`, `definitions/definitions_<locale>.toml`,
+/// `recommendations/recommendations_<locale>.toml`, or files at the top level).
+///
+/// Files are parsed before any is merged, so a malformed file leaves the loaded locales untouched.
+///
+/// # Returns
+/// The locale tags found, sorted
+pub fn load_locale_directory(path: impl AsRef<Path>) -> Result<Vec<String>, ConfigError> {
+    let root = path.as_ref();
+    let mut files = Vec::new();
+    for dir in [
+        root.to_path_buf(),
+        root.join("shots"),
+        root.join("definitions"),
+        root.join("recommendations"),
+    ] {
+        if !dir.is_dir() {
+            continue;
+        }
+        let mut paths: Vec<_> = std::fs::read_dir(&dir)
+            .map_err(|source| ConfigError::Io {
+                path: dir.clone(),
+                source,
+            })?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+        for file_path in paths {
+            files.push(load_file(&file_path, parse_locale)?);
+        }
+    }
+
+    let mut locales = locales_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut touched: Vec<String> = files
+        .into_iter()
+        .flat_map(|file| merge_locale_file(&mut locales, file))
+        .collect();
+    touched.sort();
+    touched.dedup();
+    Ok(touched)
+}
+
+/// Drop runtime-loaded locales and restore the embedded English bundle
+pub fn reset_locales() {
+    *locales_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = embedded_locales();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_shot_has_english_text() {
+        for id in ShotId::ALL {
+            let shot = describe_shot(id, DEFAULT_LOCALE);
+            assert_eq!(shot.name, id.english_name());
+            assert!(!shot.description.is_empty(), "{}", id.as_str());
+            assert!(!shot.coaching_tip.is_empty(), "{}", id.as_str());
+        }
+    }
+
+    #[test]
+    fn test_every_recommendation_has_english_text() {
+        for id in RecommendationId::ALL {
+            let text = describe_recommendation(id, "pt-BR");
+            assert_ne!(text.title, id.as_str());
+            assert!(!text.advice.is_empty(), "{}", id.as_str());
+        }
+    }
+
+    #[test]
+    fn test_unknown_locale_falls_back_to_english() {
+        let shot = describe_shot(ShotId::PushDraw, "xx-YY");
+        assert_eq!(shot, describe_shot(ShotId::PushDraw, "en"));
+        assert!(define_field("smash_factor", "xx").is_some());
+        assert!(define_field("not_a_field", "en").is_none());
+    }
+
+    #[test]
+    fn test_partial_locale_merges_per_field() {
+        // Built without touching the shared registry, which other tests read in parallel
+        let mut locales = embedded_locales();
+        let file = parse_locale(
+            r#"
+            [shots.es_MX.push_draw]
+            name = "Empuje con draw"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(merge_locale_file(&mut locales, file), vec!["es-mx"]);
+
+        let bundle = &locales["es-mx"].shots["push_draw"];
+        assert_eq!(bundle.name.as_deref(), Some("Empuje con draw"));
+        assert!(bundle.description.is_none());
+        assert_eq!(fallback_chain("es_MX"), vec!["es-mx", "es", "en"]);
+    }
+}
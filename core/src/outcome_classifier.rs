@@ -0,0 +1,157 @@
+use crate::rank_scheme::RANKS;
+use crate::vector::Vector3;
+use std::f64::consts::PI;
+
+/// Miss distance (in tolerance windows) allowed for each rank, best first; beyond the last is E
+const OUTCOME_RANK_LIMITS: [f64; 6] = [0.25, 0.5, 1.0, 1.5, 2.0, 3.0];
+
+/// Tolerance used when the caller gives a target distance but no window
+const DEFAULT_TOLERANCE_FRACTION: f64 = 0.05;
+const MIN_DEFAULT_TOLERANCE_M: f64 = 3.0;
+
+/// Default share of the outcome rank in the combined rank
+pub const DEFAULT_OUTCOME_WEIGHT: f64 = 0.5;
+
+/// Where the ball finished relative to the target
+#[derive(Debug, Clone)]
+pub struct OutcomeClassification {
+    pub outcome_name: String,
+    pub outcome_rank: String,
+    pub distance_to_target_meters: f64,
+}
+
+/// Tolerance window for a target distance when none is given
+pub fn default_target_tolerance(target_distance_m: f64) -> f64 {
+    (target_distance_m * DEFAULT_TOLERANCE_FRACTION).max(MIN_DEFAULT_TOLERANCE_M)
+}
+
+/// Classify where the ball finished relative to a target on the target line.
+///
+/// # Arguments
+/// * `resting_position` - Where the ball came to rest (landing position plus roll), in meters
+/// * `target_bearing_deg` - Direction of the target line (positive = right of the 0° line)
+/// * `target_distance_m` - Distance to the target along the target line
+/// * `tolerance_m` - Half-width of the window counted as "on target", both in depth and width
+///
+/// # Returns
+/// A label such as "On target", "Long left" or "Short", the outcome rank and the
+/// straight-line distance from the finishing position to the target
+pub fn classify_outcome(
+    resting_position: &Vector3,
+    target_bearing_deg: f64,
+    target_distance_m: f64,
+    tolerance_m: f64,
+) -> OutcomeClassification {
+    let tolerance = tolerance_m.max(f64::EPSILON);
+    let (s, c) = (target_bearing_deg * PI / 180.0).sin_cos();
+    let along_line = resting_position.x * c + resting_position.y * s;
+    let offline_m = resting_position.y * c - resting_position.x * s;
+    let depth_error = along_line - target_distance_m;
+    let distance_to_target = (depth_error.powi(2) + offline_m.powi(2)).sqrt();
+
+    let depth = if depth_error > tolerance {
+        Some("long")
+    } else if depth_error < -tolerance {
+        Some("short")
+    } else {
+        None
+    };
+    let side = if offline_m > tolerance {
+        Some("right")
+    } else if offline_m < -tolerance {
+        Some("left")
+    } else {
+        None
+    };
+
+    let outcome_name = match (depth, side) {
+        (None, None) => "On target".to_string(),
+        (Some(d), Some(s)) => capitalize(&format!("{} {}", d, s)),
+        (Some(label), None) | (None, Some(label)) => capitalize(label),
+    };
+
+    let normalized_miss = distance_to_target / tolerance;
+    let rank_index = OUTCOME_RANK_LIMITS
+        .iter()
+        .position(|limit| normalized_miss <= *limit)
+        .unwrap_or(RANKS.len() - 1);
+
+    OutcomeClassification {
+        outcome_name,
+        outcome_rank: RANKS[rank_index].to_string(),
+        distance_to_target_meters: distance_to_target,
+    }
+}
+
+/// Blend the shape rank with the outcome rank.
+///
+/// `outcome_weight` is the outcome's share (0 = shape only, 1 = outcome only). A missing or
+/// unknown shape rank (e.g. putts) yields the outcome rank.
+pub fn blend_ranks(shape_rank: &str, outcome_rank: &str, outcome_weight: f64) -> String {
+    let index = |rank: &str| RANKS.iter().position(|r| *r == rank);
+    let (Some(shape), Some(outcome)) = (index(shape_rank), index(outcome_rank)) else {
+        return outcome_rank.to_string();
+    };
+
+    let weight = outcome_weight.clamp(0.0, 1.0);
+    let blended = shape as f64 * (1.0 - weight) + outcome as f64 * weight;
+    RANKS[blended.round() as usize].to_string()
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_on_target() {
+        let outcome = classify_outcome(&Vector3::new(150.5, 1.0, 0.0), 0.0, 150.0, 5.0);
+        assert_eq!(outcome.outcome_name, "On target");
+        assert_eq!(outcome.outcome_rank, "S+");
+    }
+
+    #[test]
+    fn test_long_left() {
+        let outcome = classify_outcome(&Vector3::new(162.0, -8.0, 0.0), 0.0, 150.0, 5.0);
+        assert_eq!(outcome.outcome_name, "Long left");
+        assert!(outcome.distance_to_target_meters > 10.0);
+        assert_eq!(outcome.outcome_rank, "D");
+    }
+
+    #[test]
+    fn test_short_only() {
+        let outcome = classify_outcome(&Vector3::new(120.0, 2.0, 0.0), 0.0, 150.0, 5.0);
+        assert_eq!(outcome.outcome_name, "Short");
+        assert_eq!(outcome.outcome_rank, "E");
+    }
+
+    #[test]
+    fn test_resting_position_projected_on_target_line() {
+        // Finished 150 m out on a target line 10° right of the 0° line
+        let bearing: f64 = 10.0;
+        let (s, c) = bearing.to_radians().sin_cos();
+        let on_line = Vector3::new(150.0 * c, 150.0 * s, 0.0);
+        let outcome = classify_outcome(&on_line, bearing, 150.0, 5.0);
+        assert_eq!(outcome.outcome_name, "On target");
+        assert!(outcome.distance_to_target_meters < 1e-9);
+
+        // The same spot judged on the 0° line is right of it
+        let outcome = classify_outcome(&on_line, 0.0, 150.0, 5.0);
+        assert_eq!(outcome.outcome_name, "Right");
+    }
+
+    #[test]
+    fn test_blend_ranks() {
+        // A C-ranked push hook that finishes next to the flag
+        assert_eq!(blend_ranks("C", "S+", 0.5), "A");
+        assert_eq!(blend_ranks("C", "S+", 0.0), "C");
+        assert_eq!(blend_ranks("", "B", 0.5), "B");
+    }
+}
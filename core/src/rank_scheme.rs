@@ -0,0 +1,500 @@
+use crate::config_file::{load_file, parse_toml, ConfigError};
+use crate::shot_classifier::{Direction, Shape};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+static RANK_SCHEMES: OnceLock<RwLock<RankSchemeRegistry>> = OnceLock::new();
+static COLOR_THEMES: OnceLock<RwLock<HashMap<String, ColorTheme>>> = OnceLock::new();
+
+const DATA: &str = "rank data";
+
+/// Ranks from best to worst
+pub(crate) const RANKS: [&str; 7] = ["S+", "S", "A", "B", "C", "D", "E"];
+
+/// Theme backed by `rank_colors.toml`
+pub const CLASSIC_COLOR_THEME: &str = "classic";
+
+/// Color used when a theme has no entry for a rank
+const FALLBACK_COLOR: &str = "0xFFFFFF";
+
+macro_rules! include_classification_data {
+    ($file:literal) => {
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../shot_classification/",
+            $file
+        ))
+    };
+}
+
+/// Rank for each shape within one direction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShapeRanks {
+    pub hook: String,
+    pub draw: String,
+    pub none: String,
+    pub fade: String,
+    pub slice: String,
+}
+
+/// Direction x shape -> rank matrix used to grade regular shots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RankScheme {
+    pub pull: ShapeRanks,
+    pub straight: ShapeRanks,
+    pub push: ShapeRanks,
+}
+
+/// Rank -> display color (`0xRRGGBB`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ColorTheme {
+    pub colors: BTreeMap<String, String>,
+}
+
+impl ShapeRanks {
+    fn get(&self, shape: Shape) -> &str {
+        match shape {
+            Shape::Hook => &self.hook,
+            Shape::Draw => &self.draw,
+            Shape::None => &self.none,
+            Shape::Fade => &self.fade,
+            Shape::Slice => &self.slice,
+        }
+    }
+}
+
+impl RankScheme {
+    /// Rank of a regular direction/shape combination
+    pub fn rank(&self, direction: Direction, shape: Shape) -> &str {
+        match direction {
+            Direction::Pull => self.pull.get(shape),
+            Direction::Straight => self.straight.get(shape),
+            Direction::Push => self.push.get(shape),
+        }
+    }
+
+    /// Check that every cell holds a known rank
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (direction, row) in [
+            ("pull", &self.pull),
+            ("straight", &self.straight),
+            ("push", &self.push),
+        ] {
+            for rank in [&row.hook, &row.draw, &row.none, &row.fade, &row.slice] {
+                if !RANKS.contains(&rank.as_str()) {
+                    return Err(ConfigError::invalid(
+                        DATA,
+                        format!(
+                            "unknown rank '{}' in {} row (expected one of {})",
+                            rank,
+                            direction,
+                            RANKS.join(", ")
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ColorTheme {
+    /// Color for a rank, white if the theme has none
+    pub fn color_for(&self, rank: &str) -> String {
+        self.colors
+            .get(rank)
+            .cloned()
+            .unwrap_or_else(|| FALLBACK_COLOR.to_string())
+    }
+
+    /// Check that exactly the known ranks have a `0xRRGGBB` / `#RRGGBB` color and normalize
+    /// them to `0xRRGGBB`
+    pub fn validate(&mut self) -> Result<(), ConfigError> {
+        if let Some(unknown) = self
+            .colors
+            .keys()
+            .find(|rank| !RANKS.contains(&rank.as_str()))
+        {
+            return Err(ConfigError::invalid(
+                DATA,
+                format!(
+                    "unknown rank '{}' (expected one of {})",
+                    unknown,
+                    RANKS.join(", ")
+                ),
+            ));
+        }
+        for rank in RANKS {
+            if !self.colors.contains_key(rank) {
+                return Err(ConfigError::invalid(
+                    DATA,
+                    format!("missing color for {}", rank),
+                ));
+            }
+        }
+        for (rank, color) in self.colors.iter_mut() {
+            let hex = color
+                .trim()
+                .trim_start_matches("0x")
+                .trim_start_matches('#');
+            if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(ConfigError::invalid(
+                    DATA,
+                    format!("color '{}' for {} is not a 6-digit hex value", color, rank),
+                ));
+            }
+            *color = format!("0x{}", hex.to_uppercase());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RankSchemesFile {
+    #[serde(default)]
+    default_scheme: Option<String>,
+    schemes: HashMap<String, RankScheme>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ColorThemesFile {
+    themes: HashMap<String, ColorTheme>,
+}
+
+struct RankSchemeRegistry {
+    default_scheme: String,
+    schemes: HashMap<String, RankScheme>,
+}
+
+fn parse_rank_schemes(toml_text: &str) -> Result<RankSchemesFile, ConfigError> {
+    let file: RankSchemesFile = parse_toml(DATA, toml_text)?;
+    for (name, scheme) in &file.schemes {
+        scheme
+            .validate()
+            .map_err(|e| e.within(&format!("scheme '{}'", name)))?;
+    }
+    Ok(file)
+}
+
+fn parse_color_themes(toml_text: &str) -> Result<ColorThemesFile, ConfigError> {
+    let mut file: ColorThemesFile = parse_toml(DATA, toml_text)?;
+    for (name, theme) in file.themes.iter_mut() {
+        theme
+            .validate()
+            .map_err(|e| e.within(&format!("theme '{}'", name)))?;
+    }
+    Ok(file)
+}
+
+fn embedded_rank_schemes() -> RankSchemeRegistry {
+    let file = parse_rank_schemes(include_classification_data!("rank_schemes.toml"))
+        .expect("embedded rank_schemes.toml must be valid");
+    let default_scheme = file
+        .default_scheme
+        .expect("embedded rank_schemes.toml must name a default scheme");
+    RankSchemeRegistry {
+        default_scheme,
+        schemes: file.schemes,
+    }
+}
+
+fn embedded_color_themes() -> HashMap<String, ColorTheme> {
+    let mut themes = parse_color_themes(include_classification_data!("color_themes.toml"))
+        .expect("embedded color_themes.toml must be valid")
+        .themes;
+    themes.insert(CLASSIC_COLOR_THEME.to_string(), classic_color_theme());
+    themes
+}
+
+fn schemes_lock() -> &'static RwLock<RankSchemeRegistry> {
+    RANK_SCHEMES.get_or_init(|| RwLock::new(embedded_rank_schemes()))
+}
+
+fn themes_lock() -> &'static RwLock<HashMap<String, ColorTheme>> {
+    COLOR_THEMES.get_or_init(|| RwLock::new(embedded_color_themes()))
+}
+
+/// Named rank scheme (`beginner`, `fade_bias`, `draw_bias` or one registered at runtime)
+pub fn rank_scheme(name: &str) -> Option<RankScheme> {
+    let registry = schemes_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    registry.schemes.get(name).cloned()
+}
+
+/// Rank scheme used when a shot does not pick one
+pub fn default_rank_scheme() -> RankScheme {
+    let registry = schemes_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    registry.schemes[&registry.default_scheme].clone()
+}
+
+/// Names of the available rank schemes, sorted
+pub fn rank_scheme_names() -> Vec<String> {
+    let registry = schemes_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut names: Vec<String> = registry.schemes.keys().cloned().collect();
+    names.sort();
+    names
+}
+
+/// Add or replace a named rank scheme
+pub fn register_rank_scheme(name: &str, scheme: RankScheme) -> Result<(), ConfigError> {
+    scheme.validate()?;
+    schemes_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .schemes
+        .insert(name.to_string(), scheme);
+    Ok(())
+}
+
+/// Register every `[schemes.<name>]` table in TOML text laid out like
+/// `shot_classification/rank_schemes.toml`; `default_scheme`, if present, becomes the default.
+///
+/// # Returns
+/// The scheme names found, sorted
+pub fn load_rank_schemes_from_str(toml_text: &str) -> Result<Vec<String>, ConfigError> {
+    let file = parse_rank_schemes(toml_text)?;
+    let mut registry = schemes_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(default_scheme) = &file.default_scheme {
+        if !file.schemes.contains_key(default_scheme)
+            && !registry.schemes.contains_key(default_scheme)
+        {
+            return Err(ConfigError::invalid(
+                DATA,
+                format!("default_scheme '{}' is not defined", default_scheme),
+            ));
+        }
+    }
+
+    let mut names: Vec<String> = file.schemes.keys().cloned().collect();
+    names.sort();
+    registry.schemes.extend(file.schemes);
+    if let Some(default_scheme) = file.default_scheme {
+        registry.default_scheme = default_scheme;
+    }
+    Ok(names)
+}
+
+/// Register the rank schemes in a TOML file on disk (see [`load_rank_schemes_from_str`])
+pub fn load_rank_schemes_from_file(path: impl AsRef<Path>) -> Result<Vec<String>, ConfigError> {
+    load_file(path.as_ref(), load_rank_schemes_from_str)
+}
+
+/// Restore the rank schemes shipped with the library
+pub fn reset_rank_schemes() {
+    *schemes_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = embedded_rank_schemes();
+}
+
+/// Named color theme (`classic`, `colorblind`, `high_contrast` or one registered at runtime)
+pub fn color_theme(name: &str) -> Option<ColorTheme> {
+    themes_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(name)
+        .cloned()
+}
+
+/// Color theme used when a shot does not pick one
+pub fn default_color_theme() -> ColorTheme {
+    color_theme(CLASSIC_COLOR_THEME).unwrap_or_else(classic_color_theme)
+}
+
+/// Names of the available color themes, sorted
+pub fn color_theme_names() -> Vec<String> {
+    let mut names: Vec<String> = themes_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .keys()
+        .cloned()
+        .collect();
+    names.sort();
+    names
+}
+
+/// Add or replace a named color theme
+pub fn register_color_theme(name: &str, mut theme: ColorTheme) -> Result<(), ConfigError> {
+    theme.validate()?;
+    themes_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(name.to_string(), theme);
+    Ok(())
+}
+
+/// Register every `[themes.<name>]` table in TOML text laid out like
+/// `shot_classification/color_themes.toml`
+///
+/// # Returns
+/// The theme names found, sorted
+pub fn load_color_themes_from_str(toml_text: &str) -> Result<Vec<String>, ConfigError> {
+    let file = parse_color_themes(toml_text)?;
+    let mut names: Vec<String> = file.themes.keys().cloned().collect();
+    names.sort();
+    themes_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .extend(file.themes);
+    Ok(names)
+}
+
+/// Register the color themes in a TOML file on disk (see [`load_color_themes_from_str`])
+pub fn load_color_themes_from_file(path: impl AsRef<Path>) -> Result<Vec<String>, ConfigError> {
+    load_file(path.as_ref(), load_color_themes_from_str)
+}
+
+/// Parse and validate a single theme written like `shot_classification/rank_colors.toml`
+/// (one `"<rank>" = "0xRRGGBB"` line per rank)
+pub fn load_color_theme_from_str(toml_text: &str) -> Result<ColorTheme, ConfigError> {
+    let mut theme: ColorTheme = parse_toml(DATA, toml_text)?;
+    theme.validate()?;
+    Ok(theme)
+}
+
+/// Parse and validate a single theme from a TOML file on disk
+pub fn load_color_theme_from_file(path: impl AsRef<Path>) -> Result<ColorTheme, ConfigError> {
+    load_file(path.as_ref(), load_color_theme_from_str)
+}
+
+/// Restore the color themes shipped with the library
+pub fn reset_color_themes() {
+    *themes_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = embedded_color_themes();
+}
+
+fn classic_color_theme() -> ColorTheme {
+    load_color_theme_from_str(include_classification_data!("rank_colors.toml"))
+        .expect("embedded rank_colors.toml must be valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mirror_direction(direction: Direction) -> Direction {
+        match direction {
+            Direction::Pull => Direction::Push,
+            Direction::Straight => Direction::Straight,
+            Direction::Push => Direction::Pull,
+        }
+    }
+
+    fn mirror_shape(shape: Shape) -> Shape {
+        match shape {
+            Shape::Hook => Shape::Slice,
+            Shape::Draw => Shape::Fade,
+            Shape::None => Shape::None,
+            Shape::Fade => Shape::Draw,
+            Shape::Slice => Shape::Hook,
+        }
+    }
+
+    #[test]
+    fn test_default_scheme_matches_original_ranks() {
+        let scheme = default_rank_scheme();
+        assert_eq!(scheme.rank(Direction::Straight, Shape::None), "B");
+        assert_eq!(scheme.rank(Direction::Push, Shape::Draw), "A");
+        assert_eq!(scheme.rank(Direction::Push, Shape::Fade), "C");
+        assert_eq!(scheme.rank(Direction::Straight, Shape::Slice), "D");
+    }
+
+    #[test]
+    fn test_bias_schemes_are_mirror_images() {
+        let fade = rank_scheme("fade_bias").unwrap();
+        let draw = rank_scheme("draw_bias").unwrap();
+        for direction in [Direction::Pull, Direction::Straight, Direction::Push] {
+            for shape in [
+                Shape::Hook,
+                Shape::Draw,
+                Shape::None,
+                Shape::Fade,
+                Shape::Slice,
+            ] {
+                assert_eq!(
+                    fade.rank(direction, shape),
+                    draw.rank(mirror_direction(direction), mirror_shape(shape))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_theme_covers_every_rank() {
+        for name in [CLASSIC_COLOR_THEME, "colorblind", "high_contrast"] {
+            let theme = color_theme(name).unwrap();
+            for rank in RANKS {
+                assert!(theme.colors.contains_key(rank), "{} lacks {}", name, rank);
+            }
+        }
+        assert_eq!(default_color_theme().color_for("B"), "0x7CB342");
+    }
+
+    #[test]
+    fn test_rejects_unknown_rank() {
+        let text = r#"
+            [schemes.bad]
+            pull = { hook = "D", draw = "C", none = "B", fade = "B", slice = "C" }
+            straight = { hook = "D", draw = "A", none = "B", fade = "A", slice = "F" }
+            push = { hook = "C", draw = "A", none = "B", fade = "C", slice = "D" }
+        "#;
+        assert!(matches!(
+            parse_rank_schemes(text),
+            Err(ConfigError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_malformed_theme_errors() {
+        // Bare `S+` is not a valid TOML key
+        assert!(matches!(
+            load_color_theme_from_str("S+ = \"0x23C4FF\""),
+            Err(ConfigError::Parse { .. })
+        ));
+
+        let mut text = include_classification_data!("rank_colors.toml").to_string();
+        text.push_str("\nF = \"0x000000\"\n");
+        let error = load_color_theme_from_str(&text).unwrap_err();
+        assert!(error.to_string().contains("unknown rank 'F'"), "{}", error);
+
+        let text = include_classification_data!("rank_colors.toml").replace("0xFFC107", "yellow");
+        assert!(matches!(
+            load_color_theme_from_str(&text),
+            Err(ConfigError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_misspelled_scheme_key() {
+        let text = r#"
+            [schemes.typo]
+            pull = { hook = "D", draw = "C", none = "B", fade = "B", slice = "C" }
+            straight = { hook = "D", draw = "A", none = "B", fade = "A", slcie = "D" }
+            push = { hook = "C", draw = "A", none = "B", fade = "C", slice = "D" }
+        "#;
+        let error = parse_rank_schemes(text).err().unwrap();
+        assert!(error.to_string().contains("slcie"), "{}", error);
+    }
+
+    #[test]
+    fn test_theme_colors_are_normalized() {
+        let mut theme = default_color_theme();
+        theme.colors.insert("A".to_string(), "#00ff00".to_string());
+        theme.validate().unwrap();
+        assert_eq!(theme.color_for("A"), "0x00FF00");
+    }
+}
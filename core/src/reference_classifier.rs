@@ -0,0 +1,425 @@
+use crate::config_file::{load_file, parse_toml, ConfigError};
+use crate::rank_scheme::RANKS;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+static REFERENCE_LIBRARY: OnceLock<RwLock<ReferenceLibrary>> = OnceLock::new();
+
+const DATA: &str = "reference shots";
+
+/// Keeps an exact match from producing an infinite vote weight
+const DISTANCE_EPSILON: f64 = 0.01;
+
+macro_rules! include_reference_shots {
+    () => {
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../shot_classification/reference_shots.toml"
+        ))
+    };
+}
+
+/// Which classifier labels the shot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShotClassifierKind {
+    /// Threshold rules on HLA, spin axis, VLA and speed
+    #[default]
+    Rules,
+    /// Closest shots in the reference library, falling back to the rules when none is close
+    NearestNeighbor,
+}
+
+/// Feature vector compared against reference shots; missing features are ignored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShotFeatures {
+    pub ball_speed_meters_per_second: Option<f64>,
+    pub vertical_launch_angle_degrees: Option<f64>,
+    pub horizontal_launch_angle_degrees: Option<f64>,
+    pub spin_axis_degrees: Option<f64>,
+    /// Landing offline minus the offline the start line alone would give
+    pub curve_meters: Option<f64>,
+    pub offline_meters: Option<f64>,
+}
+
+/// Size of one unit of difference for each feature
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureScales {
+    pub ball_speed_meters_per_second: f64,
+    pub vertical_launch_angle_degrees: f64,
+    pub horizontal_launch_angle_degrees: f64,
+    pub spin_axis_degrees: f64,
+    pub curve_meters: f64,
+    pub offline_meters: f64,
+}
+
+impl Default for FeatureScales {
+    fn default() -> Self {
+        FeatureScales {
+            ball_speed_meters_per_second: 10.0,
+            vertical_launch_angle_degrees: 3.0,
+            horizontal_launch_angle_degrees: 2.0,
+            spin_axis_degrees: 3.0,
+            curve_meters: 5.0,
+            offline_meters: 8.0,
+        }
+    }
+}
+
+/// A labeled example shot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReferenceShot {
+    /// Shot ID (e.g. `push_draw`) or a custom shot type
+    pub label: String,
+    /// Display name for custom labels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Rank for custom labels, one of S+ … E
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<String>,
+    pub features: ShotFeatures,
+}
+
+/// Reference shots plus the matching parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReferenceLibrary {
+    /// Number of nearest shots that vote
+    #[serde(default = "default_k")]
+    pub k: usize,
+    /// Largest scaled distance to the nearest shot that still counts as a match
+    #[serde(default = "default_max_distance")]
+    pub max_distance: f64,
+    #[serde(default)]
+    pub scales: FeatureScales,
+    pub shots: Vec<ReferenceShot>,
+}
+
+fn default_k() -> usize {
+    3
+}
+
+fn default_max_distance() -> f64 {
+    3.0
+}
+
+/// One of the voting reference shots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceNeighbor {
+    pub label: String,
+    pub distance: f64,
+}
+
+/// Winning label and the neighbors that voted for it or against it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceMatch {
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<String>,
+    /// Share of the inverse-distance vote won by the label (0-1)
+    pub confidence: f64,
+    /// Scaled distance to the closest shot with the winning label
+    pub distance: f64,
+    /// Voting neighbors, closest first
+    pub neighbors: Vec<ReferenceNeighbor>,
+}
+
+impl ShotFeatures {
+    fn values(&self) -> [Option<f64>; 6] {
+        [
+            self.ball_speed_meters_per_second,
+            self.vertical_launch_angle_degrees,
+            self.horizontal_launch_angle_degrees,
+            self.spin_axis_degrees,
+            self.curve_meters,
+            self.offline_meters,
+        ]
+    }
+}
+
+impl FeatureScales {
+    fn values(&self) -> [f64; 6] {
+        [
+            self.ball_speed_meters_per_second,
+            self.vertical_launch_angle_degrees,
+            self.horizontal_launch_angle_degrees,
+            self.spin_axis_degrees,
+            self.curve_meters,
+            self.offline_meters,
+        ]
+    }
+}
+
+impl ReferenceLibrary {
+    /// Check matching parameters and that every shot is labeled and has a finite feature
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.shots.is_empty() {
+            return Err(ConfigError::invalid(DATA, "library has no shots"));
+        }
+        if self.k == 0 {
+            return Err(ConfigError::invalid(DATA, "k must be at least 1"));
+        }
+        if !self.max_distance.is_finite() || self.max_distance <= 0.0 {
+            return Err(ConfigError::invalid(
+                DATA,
+                "max_distance must be finite and positive",
+            ));
+        }
+        if self
+            .scales
+            .values()
+            .iter()
+            .any(|s| !s.is_finite() || *s <= 0.0)
+        {
+            return Err(ConfigError::invalid(
+                DATA,
+                "scales must be finite and positive",
+            ));
+        }
+        for (index, shot) in self.shots.iter().enumerate() {
+            if shot.label.trim().is_empty() {
+                return Err(ConfigError::invalid(
+                    DATA,
+                    format!("shot {} has no label", index + 1),
+                ));
+            }
+            if let Some(rank) = &shot.rank {
+                if !RANKS.contains(&rank.as_str()) {
+                    return Err(ConfigError::invalid(
+                        DATA,
+                        format!(
+                            "shot {} ('{}') has unknown rank '{}' (expected one of: {})",
+                            index + 1,
+                            shot.label,
+                            rank,
+                            RANKS.join(", ")
+                        ),
+                    ));
+                }
+            }
+            let values = shot.features.values();
+            if values.iter().any(|v| v.is_some_and(|v| !v.is_finite()))
+                || values.iter().all(Option::is_none)
+            {
+                return Err(ConfigError::invalid(
+                    DATA,
+                    format!(
+                        "shot {} ('{}') needs at least one finite feature",
+                        index + 1,
+                        shot.label
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Scaled RMS difference over the features both shots have, or `None` if they share none
+    fn distance(&self, a: &ShotFeatures, b: &ShotFeatures) -> Option<f64> {
+        let mut sum = 0.0;
+        let mut count = 0;
+        for ((a, b), scale) in a
+            .values()
+            .into_iter()
+            .zip(b.values())
+            .zip(self.scales.values())
+        {
+            if let (Some(a), Some(b)) = (a, b) {
+                sum += ((a - b) / scale).powi(2);
+                count += 1;
+            }
+        }
+        (count > 0).then(|| (sum / count as f64).sqrt())
+    }
+
+    /// Label a shot by inverse-distance voting among its `k` nearest reference shots.
+    ///
+    /// # Returns
+    /// The winning label, or `None` when the nearest reference is beyond `max_distance`
+    pub fn classify(&self, features: &ShotFeatures) -> Option<ReferenceMatch> {
+        let mut ranked: Vec<(f64, &ReferenceShot)> = self
+            .shots
+            .iter()
+            .filter_map(|shot| Some((self.distance(features, &shot.features)?, shot)))
+            .collect();
+        ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+        ranked.truncate(self.k);
+
+        if ranked.first()?.0 > self.max_distance {
+            return None;
+        }
+
+        // Votes per label, keeping first-seen (closest) order for ties
+        let mut votes: Vec<(&str, f64)> = Vec::new();
+        for (distance, shot) in &ranked {
+            let weight = 1.0 / (distance + DISTANCE_EPSILON);
+            match votes.iter_mut().find(|(label, _)| *label == shot.label) {
+                Some((_, total)) => *total += weight,
+                None => votes.push((&shot.label, weight)),
+            }
+        }
+        let total_weight: f64 = votes.iter().map(|(_, w)| w).sum();
+        let (label, weight) =
+            votes
+                .iter()
+                .copied()
+                .reduce(|best, next| if next.1 > best.1 { next } else { best })?;
+
+        let (distance, winner) = ranked
+            .iter()
+            .find(|(_, shot)| shot.label == label)
+            .copied()?;
+        Some(ReferenceMatch {
+            label: label.to_string(),
+            name: winner.name.clone(),
+            rank: winner.rank.clone(),
+            confidence: weight / total_weight,
+            distance,
+            neighbors: ranked
+                .iter()
+                .map(|(distance, shot)| ReferenceNeighbor {
+                    label: shot.label.clone(),
+                    distance: *distance,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Parse and validate a reference library from TOML text (`[[shots]]` entries)
+pub fn load_reference_library_from_str(toml_text: &str) -> Result<ReferenceLibrary, ConfigError> {
+    let library: ReferenceLibrary = parse_toml(DATA, toml_text)?;
+    library.validate()?;
+    Ok(library)
+}
+
+/// Parse and validate a reference library from a TOML file on disk
+pub fn load_reference_library_from_file(
+    path: impl AsRef<Path>,
+) -> Result<ReferenceLibrary, ConfigError> {
+    load_file(path.as_ref(), load_reference_library_from_str)
+}
+
+/// Reference library shipped with the library: one example per direction/shape label
+pub fn default_reference_library() -> ReferenceLibrary {
+    load_reference_library_from_str(include_reference_shots!())
+        .expect("embedded reference_shots.toml must be valid")
+}
+
+fn library_lock() -> &'static RwLock<ReferenceLibrary> {
+    REFERENCE_LIBRARY.get_or_init(|| RwLock::new(default_reference_library()))
+}
+
+/// Reference library used by the nearest-neighbor classifier
+pub fn reference_library() -> ReferenceLibrary {
+    library_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Match a shot against the current reference library without copying it
+pub(crate) fn classify_with_reference_library(features: &ShotFeatures) -> Option<ReferenceMatch> {
+    library_lock()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .classify(features)
+}
+
+/// Replace the reference library used by all subsequent nearest-neighbor classifications
+pub fn set_reference_library(library: ReferenceLibrary) -> Result<(), ConfigError> {
+    library.validate()?;
+    *library_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = library;
+    Ok(())
+}
+
+/// Restore the reference library shipped with the library
+pub fn reset_reference_library() {
+    *library_lock()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = default_reference_library();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch(hla: f64, spin_axis: f64) -> ShotFeatures {
+        ShotFeatures {
+            horizontal_launch_angle_degrees: Some(hla),
+            spin_axis_degrees: Some(spin_axis),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_default_library_matches_shapes() {
+        let library = default_reference_library();
+        let matched = library.classify(&launch(5.5, -8.0)).unwrap();
+        assert_eq!(matched.label, "push_draw");
+        assert_eq!(matched.neighbors.len(), library.k);
+        assert!(matched.confidence > 0.5);
+    }
+
+    #[test]
+    fn test_custom_shot_type_by_example() {
+        let mut library = default_reference_library();
+        for hla in [9.0, 10.0, 11.0] {
+            library.shots.push(ReferenceShot {
+                label: "block".to_string(),
+                name: Some("Block".to_string()),
+                rank: Some("C".to_string()),
+                features: ShotFeatures {
+                    curve_meters: Some(0.0),
+                    ..launch(hla, 1.0)
+                },
+            });
+        }
+        library.validate().unwrap();
+
+        let matched = library.classify(&launch(10.2, 0.5)).unwrap();
+        assert_eq!(matched.label, "block");
+        assert_eq!(matched.name.as_deref(), Some("Block"));
+        assert_eq!(matched.rank.as_deref(), Some("C"));
+    }
+
+    #[test]
+    fn test_far_shot_has_no_match() {
+        let library = default_reference_library();
+        assert!(library.classify(&launch(40.0, 60.0)).is_none());
+        assert!(library.classify(&ShotFeatures::default()).is_none());
+    }
+
+    #[test]
+    fn test_rejects_featureless_shot() {
+        let text = "[[shots]]\nlabel = \"mystery\"\nfeatures = {}";
+        assert!(matches!(
+            load_reference_library_from_str(text),
+            Err(ConfigError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_misspelled_feature_and_unknown_rank() {
+        let misspelled = "[[shots]]\nlabel = \"block\"\nfeatures = { spin_axis_degree = 1.0 }";
+        assert!(matches!(
+            load_reference_library_from_str(misspelled),
+            Err(ConfigError::Parse { .. })
+        ));
+
+        let bad_rank =
+            "[[shots]]\nlabel = \"block\"\nrank = \"Z\"\nfeatures = { spin_axis_degrees = 1.0 }";
+        assert!(matches!(
+            load_reference_library_from_str(bad_rank),
+            Err(ConfigError::Invalid { .. })
+        ));
+    }
+}
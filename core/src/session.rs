@@ -0,0 +1,415 @@
+use crate::benchmarks::{compare_to_benchmark, BenchmarkComparison, BenchmarkError};
+use crate::dispersion::{analyze_indexed, DispersionAnalysis, DispersionOptions};
+use crate::gapping::{gapping_report, GappingOptions, GappingReport};
+use crate::trends::{analyze_trends, TrendFinding, TrendOptions};
+use crate::DerivedValues;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Percentiles reported in every [`MetricStats`]
+pub const REPORTED_PERCENTILES: [f64; 4] = [10.0, 25.0, 75.0, 90.0];
+
+/// Shot metric summarized by a [`Session`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionMetric {
+    CarryDistanceMeters,
+    TotalDistanceMeters,
+    OfflineDistanceMeters,
+    BallSpeedMetersPerSecond,
+    ClubSpeedMetersPerSecond,
+    SmashFactor,
+    TotalSpinRpm,
+    VerticalLaunchAngleDegrees,
+}
+
+impl SessionMetric {
+    pub const ALL: [SessionMetric; 8] = [
+        SessionMetric::CarryDistanceMeters,
+        SessionMetric::TotalDistanceMeters,
+        SessionMetric::OfflineDistanceMeters,
+        SessionMetric::BallSpeedMetersPerSecond,
+        SessionMetric::ClubSpeedMetersPerSecond,
+        SessionMetric::SmashFactor,
+        SessionMetric::TotalSpinRpm,
+        SessionMetric::VerticalLaunchAngleDegrees,
+    ];
+
+    /// Name of the `DerivedValues` field the metric reads
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SessionMetric::CarryDistanceMeters => "carry_distance_meters",
+            SessionMetric::TotalDistanceMeters => "total_distance_meters",
+            SessionMetric::OfflineDistanceMeters => "offline_distance_meters",
+            SessionMetric::BallSpeedMetersPerSecond => "ball_speed_meters_per_second",
+            SessionMetric::ClubSpeedMetersPerSecond => "club_speed_meters_per_second",
+            SessionMetric::SmashFactor => "smash_factor",
+            SessionMetric::TotalSpinRpm => "total_spin_rpm",
+            SessionMetric::VerticalLaunchAngleDegrees => "vertical_launch_angle_degrees",
+        }
+    }
+
+    /// The metric's value for one shot, if it was provided or derived
+    pub fn value(self, shot: &DerivedValues) -> Option<f64> {
+        match self {
+            SessionMetric::CarryDistanceMeters => shot.carry_distance_meters,
+            SessionMetric::TotalDistanceMeters => shot.total_distance_meters,
+            SessionMetric::OfflineDistanceMeters => shot.offline_distance_meters,
+            SessionMetric::BallSpeedMetersPerSecond => shot.ball_speed_meters_per_second,
+            SessionMetric::ClubSpeedMetersPerSecond => shot.club_speed_meters_per_second,
+            SessionMetric::SmashFactor => shot.smash_factor,
+            SessionMetric::TotalSpinRpm => shot.total_spin_rpm,
+            SessionMetric::VerticalLaunchAngleDegrees => shot.vertical_launch_angle_degrees,
+        }
+        .filter(|value| value.is_finite())
+    }
+}
+
+/// Summary statistics for one metric over a group of shots
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricStats {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+
+    /// Sample standard deviation (zero for a single shot)
+    pub standard_deviation: f64,
+
+    pub min: f64,
+    pub max: f64,
+    pub p10: f64,
+    pub p25: f64,
+    pub p75: f64,
+    pub p90: f64,
+}
+
+impl MetricStats {
+    /// Statistics for a set of values, or `None` when there are none
+    pub fn from_values(values: &[f64]) -> Option<Self> {
+        let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = if sorted.len() > 1 {
+            sorted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        let [p10, p25, p75, p90] = REPORTED_PERCENTILES.map(|p| percentile_of_sorted(&sorted, p));
+
+        Some(MetricStats {
+            count: sorted.len(),
+            mean,
+            median: percentile_of_sorted(&sorted, 50.0),
+            standard_deviation: variance.sqrt(),
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            p10,
+            p25,
+            p75,
+            p90,
+        })
+    }
+}
+
+/// Linearly interpolated percentile (0-100) of an ascending, non-empty slice
+fn percentile_of_sorted(sorted: &[f64], percent: f64) -> f64 {
+    let rank = percent.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Statistics for the shots hit with one club
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClubStats {
+    /// Club label as given in the input; `None` groups shots without one
+    pub club: Option<String>,
+    pub shot_count: usize,
+
+    /// Only metrics present on at least one shot are listed
+    pub metrics: BTreeMap<SessionMetric, MetricStats>,
+}
+
+/// Per-club and overall statistics for a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub shot_count: usize,
+
+    /// Clubs in the order they were first hit
+    pub clubs: Vec<ClubStats>,
+
+    /// All shots together, regardless of club
+    pub overall: ClubStats,
+}
+
+/// Which of a session's shots a query looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClubSelector<'a> {
+    /// Every shot, regardless of club
+    All,
+    /// Shots without a `club` label
+    Untagged,
+    /// Shots tagged with this club label
+    Club(&'a str),
+}
+
+impl ClubSelector<'_> {
+    pub fn matches(&self, shot: &DerivedValues) -> bool {
+        match self {
+            ClubSelector::All => true,
+            ClubSelector::Untagged => shot.club.is_none(),
+            ClubSelector::Club(club) => shot.club.as_deref() == Some(*club),
+        }
+    }
+}
+
+/// Shots from one practice session, in the order they were hit
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Session {
+    shots: Vec<DerivedValues>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Session::default()
+    }
+
+    /// Record a shot; it is grouped by its `club` field
+    pub fn add(&mut self, shot: DerivedValues) {
+        self.shots.push(shot);
+    }
+
+    pub fn shots(&self) -> &[DerivedValues] {
+        &self.shots
+    }
+
+    pub fn len(&self) -> usize {
+        self.shots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shots.is_empty()
+    }
+
+    /// Distinct clubs in the order they were first hit
+    pub fn clubs(&self) -> Vec<Option<String>> {
+        let mut clubs: Vec<Option<String>> = Vec::new();
+        for shot in &self.shots {
+            if !clubs.contains(&shot.club) {
+                clubs.push(shot.club.clone());
+            }
+        }
+        clubs
+    }
+
+    /// Values of a metric for the selected shots
+    pub fn values(&self, metric: SessionMetric, selector: ClubSelector) -> Vec<f64> {
+        self.values_where(metric, |shot| selector.matches(shot))
+    }
+
+    /// Statistics of a metric for the selected shots
+    pub fn stats(&self, metric: SessionMetric, selector: ClubSelector) -> Option<MetricStats> {
+        MetricStats::from_values(&self.values(metric, selector))
+    }
+
+    /// Any percentile (0-100) of a metric for the selected shots
+    pub fn percentile(
+        &self,
+        metric: SessionMetric,
+        selector: ClubSelector,
+        percent: f64,
+    ) -> Option<f64> {
+        let mut values = self.values(metric, selector);
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+        Some(percentile_of_sorted(&values, percent))
+    }
+
+    /// Landing pattern for the selected shots; outlier indices refer to [`Session::shots`]
+    pub fn dispersion(
+        &self,
+        selector: ClubSelector,
+        options: &DispersionOptions,
+    ) -> Option<DispersionAnalysis> {
+        analyze_indexed(
+            self.shots
+                .iter()
+                .enumerate()
+                .filter(|(_, shot)| selector.matches(shot)),
+            options,
+        )
+    }
+
+    /// Stock carries, gaps and loft suggestions for the clubs in the session
+    pub fn gapping_report(&self, options: &GappingOptions) -> GappingReport {
+        gapping_report(self, options)
+    }
+
+    /// Significant drifts, such as club speed fading late in the session, per club
+    pub fn trends(&self, options: &TrendOptions) -> Vec<TrendFinding> {
+        analyze_trends(self, options)
+    }
+
+    /// One club's shots measured against a benchmark band (the default band when `band` is
+    /// `None`)
+    pub fn compare_to_benchmark(
+        &self,
+        club: &str,
+        band: Option<&str>,
+    ) -> Result<BenchmarkComparison, BenchmarkError> {
+        let shots: Vec<DerivedValues> = self
+            .shots
+            .iter()
+            .filter(|shot| shot.club.as_deref() == Some(club))
+            .cloned()
+            .collect();
+        compare_to_benchmark(&shots, band, Some(club))
+    }
+
+    /// Statistics for every metric, grouped by club and overall
+    pub fn summary(&self) -> SessionSummary {
+        SessionSummary {
+            shot_count: self.shots.len(),
+            clubs: self
+                .clubs()
+                .into_iter()
+                .map(|club| {
+                    let stats = self.group_stats(|shot| shot.club == club);
+                    ClubStats { club, ..stats }
+                })
+                .collect(),
+            overall: self.group_stats(|_| true),
+        }
+    }
+
+    fn values_where(
+        &self,
+        metric: SessionMetric,
+        include: impl Fn(&DerivedValues) -> bool,
+    ) -> Vec<f64> {
+        self.shots
+            .iter()
+            .filter(|shot| include(shot))
+            .filter_map(|shot| metric.value(shot))
+            .collect()
+    }
+
+    fn group_stats(&self, include: impl Fn(&DerivedValues) -> bool) -> ClubStats {
+        ClubStats {
+            club: None,
+            shot_count: self.shots.iter().filter(|shot| include(shot)).count(),
+            metrics: SessionMetric::ALL
+                .into_iter()
+                .filter_map(|metric| {
+                    let stats = MetricStats::from_values(&self.values_where(metric, &include))?;
+                    Some((metric, stats))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl FromIterator<DerivedValues> for Session {
+    fn from_iter<I: IntoIterator<Item = DerivedValues>>(iter: I) -> Self {
+        Session {
+            shots: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<DerivedValues> for Session {
+    fn extend<I: IntoIterator<Item = DerivedValues>>(&mut self, iter: I) {
+        self.shots.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shot(club: Option<&str>, carry: f64, ball_speed: f64) -> DerivedValues {
+        let mut shot = DerivedValues::new();
+        shot.club = club.map(str::to_string);
+        shot.carry_distance_meters = Some(carry);
+        shot.ball_speed_meters_per_second = Some(ball_speed);
+        shot
+    }
+
+    #[test]
+    fn test_metric_stats() {
+        let stats = MetricStats::from_values(&[4.0, 1.0, 3.0, 2.0, 5.0]).unwrap();
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.median, 3.0);
+        assert!((stats.standard_deviation - 2.5_f64.sqrt()).abs() < 1e-12);
+        assert_eq!((stats.min, stats.max), (1.0, 5.0));
+        assert!((stats.p10 - 1.4).abs() < 1e-12);
+        assert_eq!((stats.p25, stats.p75), (2.0, 4.0));
+
+        let single = MetricStats::from_values(&[7.0]).unwrap();
+        assert_eq!((single.median, single.standard_deviation), (7.0, 0.0));
+        assert!(MetricStats::from_values(&[f64::NAN]).is_none());
+    }
+
+    #[test]
+    fn test_summary_groups_by_club() {
+        let session: Session = [
+            shot(Some("7i"), 140.0, 52.0),
+            shot(Some("Driver"), 230.0, 72.0),
+            shot(Some("7i"), 150.0, 54.0),
+            shot(None, 100.0, 40.0),
+        ]
+        .into_iter()
+        .collect();
+
+        let summary = session.summary();
+        assert_eq!(summary.shot_count, 4);
+        let clubs: Vec<_> = summary.clubs.iter().map(|c| c.club.as_deref()).collect();
+        assert_eq!(clubs, vec![Some("7i"), Some("Driver"), None]);
+
+        let iron = &summary.clubs[0];
+        assert_eq!(iron.shot_count, 2);
+        assert_eq!(
+            iron.metrics[&SessionMetric::CarryDistanceMeters].mean,
+            145.0
+        );
+        assert!(!iron.metrics.contains_key(&SessionMetric::SmashFactor));
+        assert_eq!(
+            summary.overall.metrics[&SessionMetric::BallSpeedMetersPerSecond].max,
+            72.0
+        );
+        assert_eq!(
+            session.percentile(
+                SessionMetric::CarryDistanceMeters,
+                ClubSelector::Club("7i"),
+                50.0
+            ),
+            Some(145.0)
+        );
+        assert_eq!(
+            session
+                .stats(SessionMetric::CarryDistanceMeters, ClubSelector::Untagged)
+                .map(|stats| stats.count),
+            Some(1)
+        );
+        assert_eq!(
+            session
+                .values(SessionMetric::CarryDistanceMeters, ClubSelector::All)
+                .len(),
+            4
+        );
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(
+            json["clubs"][0]["metrics"]["carry_distance_meters"]["median"],
+            145.0
+        );
+    }
+}
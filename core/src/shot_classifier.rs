@@ -1,243 +1,561 @@
-use std::collections::HashMap;
-use std::sync::OnceLock;
+use crate::classifier_config::{classifier_config, ClassifierConfig};
+use crate::rank_scheme::{default_color_theme, default_rank_scheme, ColorTheme, RankScheme};
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
-static RANK_COLORS: OnceLock<HashMap<String, String>> = OnceLock::new();
+/// Distance (degrees) from the nearest boundary at which confidence reaches 1.0
+const FULL_CONFIDENCE_MARGIN_DEGREES: f64 = 2.0;
 
-#[derive(Debug, Clone)]
+/// Result of classifying a shot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ShotClassificationResult {
+    pub shot_id: ShotId,
     pub shot_name: String,
     pub shot_rank: String,
     pub shot_color_rgb: String,
+    /// 0.5 on a direction/shape boundary, 1.0 once clear of every boundary
+    pub confidence: f64,
+    /// Neighbouring labels, closest first (empty for special shots)
+    pub alternatives: Vec<ShotAlternative>,
+}
+
+/// Thresholds, grading and colors used by [`classify_shot`]
+#[derive(Debug, Clone)]
+pub struct ClassificationOptions {
+    pub config: ClassifierConfig,
+    pub rank_scheme: RankScheme,
+    pub color_theme: ColorTheme,
+    /// ID or name of the previous shot in a session, for hysteresis
+    pub previous_shot_name: Option<String>,
+}
+
+impl Default for ClassificationOptions {
+    /// Process-wide classifier config, default rank scheme and classic colors
+    fn default() -> Self {
+        ClassificationOptions {
+            config: classifier_config(),
+            rank_scheme: default_rank_scheme(),
+            color_theme: default_color_theme(),
+            previous_shot_name: None,
+        }
+    }
+}
+
+/// A label the shot would have received on the other side of a nearby boundary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShotAlternative {
+    pub shot_id: ShotId,
+    pub shot_name: String,
+    pub shot_rank: String,
+    /// Change in HLA and/or spin axis needed to reach this label
+    pub boundary_distance_degrees: f64,
 }
 
 /// Direction of the shot based on horizontal launch angle
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Direction {
-    Pull,     // HLA < -3.0°
-    Straight, // -3.0° <= HLA <= 3.0°
-    Push,     // HLA > 3.0°
+    Pull,     // HLA < -straight_max_hla
+    Straight, // |HLA| <= straight_max_hla
+    Push,     // HLA > straight_max_hla
 }
 
 /// Shape/curvature of the shot based on spin axis
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Shape {
-    Hook,  // spin_axis < -12.0°
-    Draw,  // -12.0° <= spin_axis < -3.0°
-    None,  // -3.0° <= spin_axis <= 3.0°
-    Fade,  // 3.0° < spin_axis <= 12.0°
-    Slice, // spin_axis > 12.0°
+    Hook,  // spin_axis < -severe_curve_min
+    Draw,  // -severe_curve_min <= spin_axis < -curve_min
+    None,  // |spin_axis| <= curve_min
+    Fade,  // curve_min < spin_axis <= severe_curve_min
+    Slice, // spin_axis > severe_curve_min
+}
+
+/// Stable, machine-readable shot identifier
+///
+/// Serializes to the snake_case keys used in `locales/shots/shots_en.toml` (e.g. `push_draw`),
+/// so clients can localize and switch on shots without parsing the English `shot_name`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShotId {
+    Putt,
+    WormBurner,
+    RightShank,
+    LeftShank,
+    DuckHook,
+    BananaSlice,
+    BabyPushDraw,
+    BabyPullFade,
+    Pull,
+    PullHook,
+    PullDraw,
+    PullFade,
+    PullSlice,
+    Straight,
+    StraightHook,
+    StraightDraw,
+    StraightFade,
+    StraightSlice,
+    Push,
+    PushHook,
+    PushDraw,
+    PushFade,
+    PushSlice,
+}
+
+impl ShotId {
+    pub const ALL: [ShotId; 23] = [
+        ShotId::Putt,
+        ShotId::WormBurner,
+        ShotId::RightShank,
+        ShotId::LeftShank,
+        ShotId::DuckHook,
+        ShotId::BananaSlice,
+        ShotId::BabyPushDraw,
+        ShotId::BabyPullFade,
+        ShotId::Pull,
+        ShotId::PullHook,
+        ShotId::PullDraw,
+        ShotId::PullFade,
+        ShotId::PullSlice,
+        ShotId::Straight,
+        ShotId::StraightHook,
+        ShotId::StraightDraw,
+        ShotId::StraightFade,
+        ShotId::StraightSlice,
+        ShotId::Push,
+        ShotId::PushHook,
+        ShotId::PushDraw,
+        ShotId::PushFade,
+        ShotId::PushSlice,
+    ];
+
+    /// Identifier for a regular direction/shape combination
+    pub fn from_parts(direction: Direction, shape: Shape) -> Self {
+        match (direction, shape) {
+            (Direction::Pull, Shape::None) => ShotId::Pull,
+            (Direction::Pull, Shape::Hook) => ShotId::PullHook,
+            (Direction::Pull, Shape::Draw) => ShotId::PullDraw,
+            (Direction::Pull, Shape::Fade) => ShotId::PullFade,
+            (Direction::Pull, Shape::Slice) => ShotId::PullSlice,
+            (Direction::Straight, Shape::None) => ShotId::Straight,
+            (Direction::Straight, Shape::Hook) => ShotId::StraightHook,
+            (Direction::Straight, Shape::Draw) => ShotId::StraightDraw,
+            (Direction::Straight, Shape::Fade) => ShotId::StraightFade,
+            (Direction::Straight, Shape::Slice) => ShotId::StraightSlice,
+            (Direction::Push, Shape::None) => ShotId::Push,
+            (Direction::Push, Shape::Hook) => ShotId::PushHook,
+            (Direction::Push, Shape::Draw) => ShotId::PushDraw,
+            (Direction::Push, Shape::Fade) => ShotId::PushFade,
+            (Direction::Push, Shape::Slice) => ShotId::PushSlice,
+        }
+    }
+
+    /// Direction and shape of a regular shot (`None` for special shots such as shanks)
+    pub fn parts(self) -> Option<(Direction, Shape)> {
+        Direction::ORDER.into_iter().find_map(|direction| {
+            Shape::ORDER
+                .into_iter()
+                .find(|shape| ShotId::from_parts(direction, *shape) == self)
+                .map(|shape| (direction, shape))
+        })
+    }
+
+    /// Locale key, e.g. `push_draw`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ShotId::Putt => "putt",
+            ShotId::WormBurner => "worm_burner",
+            ShotId::RightShank => "right_shank",
+            ShotId::LeftShank => "left_shank",
+            ShotId::DuckHook => "duck_hook",
+            ShotId::BananaSlice => "banana_slice",
+            ShotId::BabyPushDraw => "baby_push_draw",
+            ShotId::BabyPullFade => "baby_pull_fade",
+            ShotId::Pull => "pull",
+            ShotId::PullHook => "pull_hook",
+            ShotId::PullDraw => "pull_draw",
+            ShotId::PullFade => "pull_fade",
+            ShotId::PullSlice => "pull_slice",
+            ShotId::Straight => "straight",
+            ShotId::StraightHook => "straight_hook",
+            ShotId::StraightDraw => "straight_draw",
+            ShotId::StraightFade => "straight_fade",
+            ShotId::StraightSlice => "straight_slice",
+            ShotId::Push => "push",
+            ShotId::PushHook => "push_hook",
+            ShotId::PushDraw => "push_draw",
+            ShotId::PushFade => "push_fade",
+            ShotId::PushSlice => "push_slice",
+        }
+    }
+
+    /// English label reported as `shot_name`, e.g. "Push Draw"
+    pub fn english_name(self) -> &'static str {
+        match self {
+            ShotId::Putt => "Putt",
+            ShotId::WormBurner => "Worm Burner",
+            ShotId::RightShank => "Right Shank",
+            ShotId::LeftShank => "Left Shank",
+            ShotId::DuckHook => "Duck Hook",
+            ShotId::BananaSlice => "Banana Slice",
+            ShotId::BabyPushDraw => "Baby Push Draw",
+            ShotId::BabyPullFade => "Baby Pull Fade",
+            ShotId::Pull => "Pull",
+            ShotId::PullHook => "Pull Hook",
+            ShotId::PullDraw => "Pull Draw",
+            ShotId::PullFade => "Pull Fade",
+            ShotId::PullSlice => "Pull Slice",
+            ShotId::Straight => "Straight",
+            ShotId::StraightHook => "Straight Hook",
+            ShotId::StraightDraw => "Straight Draw",
+            ShotId::StraightFade => "Straight Fade",
+            ShotId::StraightSlice => "Straight Slice",
+            ShotId::Push => "Push",
+            ShotId::PushHook => "Push Hook",
+            ShotId::PushDraw => "Push Draw",
+            ShotId::PushFade => "Push Fade",
+            ShotId::PushSlice => "Push Slice",
+        }
+    }
+
+    /// Look up a shot by its ID or English name
+    pub fn from_label(label: &str) -> Option<Self> {
+        let label = label.trim();
+        ShotId::ALL
+            .into_iter()
+            .find(|id| id.as_str() == label || id.english_name() == label)
+    }
+}
+
+impl fmt::Display for ShotId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.english_name())
+    }
 }
 
 impl Direction {
-    fn from_hla(hla: f64) -> Self {
-        if hla < -3.0 {
+    const ORDER: [Direction; 3] = [Direction::Pull, Direction::Straight, Direction::Push];
+
+    fn boundaries(config: &ClassifierConfig) -> [f64; 2] {
+        let limit = config.straight_max_hla_degrees;
+        [-limit, limit]
+    }
+
+    fn index(self) -> usize {
+        Self::ORDER.iter().position(|d| *d == self).unwrap_or(1)
+    }
+
+    fn from_hla(hla: f64, config: &ClassifierConfig) -> Self {
+        let limit = config.straight_max_hla_degrees;
+        if hla < -limit {
             Direction::Pull
-        } else if hla > 3.0 {
+        } else if hla > limit {
             Direction::Push
         } else {
             Direction::Straight
         }
     }
-
-    fn as_str(&self) -> &'static str {
-        match self {
-            Direction::Pull => "Pull",
-            Direction::Straight => "Straight",
-            Direction::Push => "Push",
-        }
-    }
 }
 
 impl Shape {
-    fn from_spin_axis(spin_axis: f64) -> Self {
-        if spin_axis < -12.0 {
+    const ORDER: [Shape; 5] = [
+        Shape::Hook,
+        Shape::Draw,
+        Shape::None,
+        Shape::Fade,
+        Shape::Slice,
+    ];
+
+    fn boundaries(config: &ClassifierConfig) -> [f64; 4] {
+        let curve = config.curve_min_spin_axis_degrees;
+        let severe = config.severe_curve_min_spin_axis_degrees;
+        [-severe, -curve, curve, severe]
+    }
+
+    fn index(self) -> usize {
+        Self::ORDER.iter().position(|s| *s == self).unwrap_or(2)
+    }
+
+    fn from_spin_axis(spin_axis: f64, config: &ClassifierConfig) -> Self {
+        let curve = config.curve_min_spin_axis_degrees;
+        let severe = config.severe_curve_min_spin_axis_degrees;
+        if spin_axis < -severe {
             Shape::Hook
-        } else if spin_axis < -3.0 {
+        } else if spin_axis < -curve {
             Shape::Draw
-        } else if spin_axis > 12.0 {
+        } else if spin_axis > severe {
             Shape::Slice
-        } else if spin_axis > 3.0 {
+        } else if spin_axis > curve {
             Shape::Fade
         } else {
             Shape::None
         }
     }
+}
 
-    fn as_str(&self) -> Option<&'static str> {
-        match self {
-            Shape::Hook => Some("Hook"),
-            Shape::Draw => Some("Draw"),
-            Shape::None => None,
-            Shape::Fade => Some("Fade"),
-            Shape::Slice => Some("Slice"),
-        }
+/// Helper to create a special case shot result
+fn special_shot(shot_id: ShotId, rank: &str, theme: &ColorTheme) -> ShotClassificationResult {
+    ShotClassificationResult {
+        shot_id,
+        shot_name: shot_id.english_name().to_string(),
+        shot_rank: rank.to_string(),
+        shot_color_rgb: theme.color_for(rank),
+        confidence: 1.0,
+        alternatives: Vec::new(),
     }
 }
 
-macro_rules! include_rank_colors {
-    () => {
-        include_str!(concat!(
-            env!("CARGO_MANIFEST_DIR"),
-            "/../shot_classification/rank_colors.toml"
-        ))
-    };
+/// Lower and upper edge of a region between sorted boundaries
+fn region_bounds(boundaries: &[f64], index: usize) -> (f64, f64) {
+    let lower = index
+        .checked_sub(1)
+        .map_or(f64::NEG_INFINITY, |i| boundaries[i]);
+    let upper = boundaries.get(index).copied().unwrap_or(f64::INFINITY);
+    (lower, upper)
 }
 
-/// Helper to create a special case shot result
-fn special_shot(name: &str, rank: &str) -> ShotClassificationResult {
-    ShotClassificationResult {
-        shot_name: name.to_string(),
-        shot_rank: rank.to_string(),
-        shot_color_rgb: rank_color_for(rank),
+/// Distance from the value to the nearest edge of its region (negative when held outside it
+/// by hysteresis) and the region across that edge
+fn nearest_edge(value: f64, boundaries: &[f64], index: usize) -> (f64, usize) {
+    let (lower, upper) = region_bounds(boundaries, index);
+    let to_lower = value - lower;
+    let to_upper = upper - value;
+    if to_lower < to_upper {
+        (to_lower, index - 1)
+    } else {
+        (to_upper, index + 1)
+    }
+}
+
+/// Keep the previous region if the value has only crossed into a neighbour by less than the margin
+fn hold_previous(
+    value: f64,
+    raw: usize,
+    previous: Option<usize>,
+    boundaries: &[f64],
+    margin: f64,
+) -> usize {
+    match previous {
+        Some(prev) if prev.abs_diff(raw) == 1 => {
+            let (lower, upper) = region_bounds(boundaries, prev);
+            if value >= lower - margin && value <= upper + margin {
+                prev
+            } else {
+                raw
+            }
+        }
+        _ => raw,
     }
 }
 
+fn alternative(
+    direction: Direction,
+    shape: Shape,
+    distance: f64,
+    scheme: &RankScheme,
+) -> ShotAlternative {
+    let shot_id = ShotId::from_parts(direction, shape);
+    ShotAlternative {
+        shot_id,
+        shot_name: shot_id.english_name().to_string(),
+        shot_rank: scheme.rank(direction, shape).to_string(),
+        boundary_distance_degrees: distance,
+    }
+}
+
+/// Classify a shot from its launch conditions.
+///
+/// Angles are golfer-relative (right-handed frame, measured from the target line).
+///
+/// # Arguments
+/// * `ball_speed_mps` - Ball speed in m/s
+/// * `vertical_launch_angle_deg` - Vertical launch angle
+/// * `horizontal_launch_angle_deg` - Horizontal launch angle (positive = right)
+/// * `_total_spin_rpm` - Total spin (currently unused)
+/// * `spin_axis_deg` - Spin axis (positive = fade/slice)
+/// * `options` - Thresholds, rank scheme, color theme and previous shot
+///
+/// # Returns
+/// The classification, or `None` if the shot cannot be classified
 pub fn classify_shot(
     ball_speed_mps: f64,
     vertical_launch_angle_deg: f64,
     horizontal_launch_angle_deg: f64,
     _total_spin_rpm: f64,
     spin_axis_deg: f64,
+    options: &ClassificationOptions,
 ) -> Option<ShotClassificationResult> {
+    let config = &options.config;
+    let theme = &options.color_theme;
+
     // Putt override: very low launch angle and slow ball speed
-    if vertical_launch_angle_deg.abs() < 0.1 && ball_speed_mps < 15.0 {
+    if vertical_launch_angle_deg.abs() < config.putt_max_vla_degrees
+        && ball_speed_mps < config.putt_max_ball_speed_mps
+    {
         return Some(ShotClassificationResult {
-            shot_name: "Putt".to_string(),
+            shot_id: ShotId::Putt,
+            shot_name: ShotId::Putt.english_name().to_string(),
             shot_rank: String::new(),
             shot_color_rgb: "0x808080".to_string(),
+            confidence: 1.0,
+            alternatives: Vec::new(),
         });
     }
 
     // Worm Burner: low launch angle with decent speed
-    // VLA < 5 and ball_speed > 20
-    if vertical_launch_angle_deg < 5.0 && ball_speed_mps > 20.0 {
-        return Some(special_shot("Worm Burner", "E"));
+    if vertical_launch_angle_deg < config.worm_burner_max_vla_degrees
+        && ball_speed_mps > config.worm_burner_min_ball_speed_mps
+    {
+        return Some(special_shot(ShotId::WormBurner, "E", theme));
     }
 
     // Right Shank: extreme right direction, high launch
-    // HLA > 12, VLA > 12
-    if horizontal_launch_angle_deg > 12.0 && vertical_launch_angle_deg > 12.0 {
-        return Some(special_shot("Right Shank", "E"));
+    if horizontal_launch_angle_deg > config.shank_min_hla_degrees
+        && vertical_launch_angle_deg > config.shank_min_vla_degrees
+    {
+        return Some(special_shot(ShotId::RightShank, "E", theme));
     }
 
     // Left Shank: extreme left direction, high launch
-    // HLA < -12, VLA > 12
-    if horizontal_launch_angle_deg < -12.0 && vertical_launch_angle_deg > 12.0 {
-        return Some(special_shot("Left Shank", "E"));
+    if horizontal_launch_angle_deg < -config.shank_min_hla_degrees
+        && vertical_launch_angle_deg > config.shank_min_vla_degrees
+    {
+        return Some(special_shot(ShotId::LeftShank, "E", theme));
     }
 
     // Duck Hook: extreme draw/hook that dives low and left
-    // ball_speed > 30, VLA < 15, spin_axis < -25
-    if ball_speed_mps > 30.0 && vertical_launch_angle_deg < 15.0 && spin_axis_deg < -25.0 {
-        return Some(special_shot("Duck Hook", "E"));
+    if ball_speed_mps > config.duck_hook_min_ball_speed_mps
+        && vertical_launch_angle_deg < config.duck_hook_max_vla_degrees
+        && spin_axis_deg < -config.duck_hook_min_spin_axis_degrees
+    {
+        return Some(special_shot(ShotId::DuckHook, "E", theme));
     }
 
     // Banana Slice: extreme slice that balloons high and right
-    // ball_speed > 30, VLA > 20, spin_axis > 25
-    if ball_speed_mps > 30.0 && vertical_launch_angle_deg > 20.0 && spin_axis_deg > 25.0 {
-        return Some(special_shot("Banana Slice", "E"));
+    if ball_speed_mps > config.banana_slice_min_ball_speed_mps
+        && vertical_launch_angle_deg > config.banana_slice_min_vla_degrees
+        && spin_axis_deg > config.banana_slice_min_spin_axis_degrees
+    {
+        return Some(special_shot(ShotId::BananaSlice, "E", theme));
     }
 
-    // Baby shots: opposite signs with small magnitudes (both < 2.0)
+    // Baby shots: opposite signs with small magnitudes
     // Baby Push Draw: HLA > 0 (push) and spin_axis < 0 (draw), both small
     // Baby Pull Fade: HLA < 0 (pull) and spin_axis > 0 (fade), both small
     let hla_abs = horizontal_launch_angle_deg.abs();
     let spin_abs = spin_axis_deg.abs();
-    if hla_abs < 2.0 && spin_abs < 2.0 {
+    if hla_abs < config.baby_shot_max_degrees && spin_abs < config.baby_shot_max_degrees {
         if horizontal_launch_angle_deg > 0.0 && spin_axis_deg < 0.0 {
-            return Some(special_shot("Baby Push Draw", "S+"));
+            return Some(special_shot(ShotId::BabyPushDraw, "S+", theme));
         } else if horizontal_launch_angle_deg < 0.0 && spin_axis_deg > 0.0 {
-            return Some(special_shot("Baby Pull Fade", "S"));
+            return Some(special_shot(ShotId::BabyPullFade, "S", theme));
         }
         // If both same sign or one is zero, fall through to normal classification
     }
 
-    // Determine direction and shape
-    let direction = Direction::from_hla(horizontal_launch_angle_deg);
-    let shape = Shape::from_spin_axis(spin_axis_deg);
+    // Determine direction and shape, holding the previous shot's label near a boundary
+    let previous = options
+        .previous_shot_name
+        .as_deref()
+        .and_then(ShotId::from_label)
+        .and_then(ShotId::parts);
+    let margin = config.hysteresis_degrees;
+    let direction_bounds = Direction::boundaries(config);
+    let shape_bounds = Shape::boundaries(config);
+    let direction_index = hold_previous(
+        horizontal_launch_angle_deg,
+        Direction::from_hla(horizontal_launch_angle_deg, config).index(),
+        previous.map(|(direction, _)| direction.index()),
+        &direction_bounds,
+        margin,
+    );
+    let shape_index = hold_previous(
+        spin_axis_deg,
+        Shape::from_spin_axis(spin_axis_deg, config).index(),
+        previous.map(|(_, shape)| shape.index()),
+        &shape_bounds,
+        margin,
+    );
+    let direction = Direction::ORDER[direction_index];
+    let shape = Shape::ORDER[shape_index];
 
-    // Build shot name
-    let shot_name = match shape.as_str() {
-        Some(shape_str) => format!("{} {}", direction.as_str(), shape_str),
-        None => direction.as_str().to_string(),
-    };
+    // Confidence and runner-up labels from the nearest boundary on each axis
+    let (direction_distance, next_direction) = nearest_edge(
+        horizontal_launch_angle_deg,
+        &direction_bounds,
+        direction_index,
+    );
+    let (shape_distance, next_shape) = nearest_edge(spin_axis_deg, &shape_bounds, shape_index);
+    let nearest = direction_distance.min(shape_distance);
+    let confidence = (0.5 + 0.5 * nearest / FULL_CONFIDENCE_MARGIN_DEGREES).clamp(0.0, 1.0);
+
+    let next_direction = Direction::ORDER[next_direction];
+    let next_shape = Shape::ORDER[next_shape];
+    let mut alternatives = vec![
+        alternative(
+            next_direction,
+            shape,
+            direction_distance.abs(),
+            &options.rank_scheme,
+        ),
+        alternative(
+            direction,
+            next_shape,
+            shape_distance.abs(),
+            &options.rank_scheme,
+        ),
+        alternative(
+            next_direction,
+            next_shape,
+            direction_distance.hypot(shape_distance),
+            &options.rank_scheme,
+        ),
+    ];
+    alternatives.sort_by(|a, b| {
+        a.boundary_distance_degrees
+            .total_cmp(&b.boundary_distance_degrees)
+    });
 
     // Assign rank based on shot type
-    let shot_rank = get_shot_rank(direction, shape);
-    let shot_color_rgb = rank_color_for(&shot_rank);
+    let shot_rank = options.rank_scheme.rank(direction, shape).to_string();
+    let shot_color_rgb = theme.color_for(&shot_rank);
 
+    let shot_id = ShotId::from_parts(direction, shape);
     Some(ShotClassificationResult {
-        shot_name,
+        shot_id,
+        shot_name: shot_id.english_name().to_string(),
         shot_rank,
         shot_color_rgb,
+        confidence,
+        alternatives,
     })
 }
 
-fn get_shot_rank(direction: Direction, shape: Shape) -> String {
-    // Ranks focused toward beginners - push/fade shots imply better swing mechanics
-    match (direction, shape) {
-        // A: Shots with controlled curve
-        (Direction::Straight, Shape::Draw) => "A".to_string(),
-        (Direction::Straight, Shape::Fade) => "A".to_string(),
-        (Direction::Push, Shape::Draw) => "A".to_string(),
-
-        // B: No curve shots (two-way miss potential)
-        (Direction::Straight, Shape::None) => "B".to_string(),
-        (Direction::Pull, Shape::None) => "B".to_string(),
-        (Direction::Push, Shape::None) => "B".to_string(),
-        (Direction::Pull, Shape::Fade) => "B".to_string(),
-
-        // C: Shots curving away from target
-        (Direction::Pull, Shape::Draw) => "C".to_string(),
-        (Direction::Push, Shape::Fade) => "C".to_string(),
-        (Direction::Push, Shape::Hook) => "C".to_string(),
-        (Direction::Pull, Shape::Slice) => "C".to_string(),
-
-        // D: Extreme curves (hooks/slices)
-        (Direction::Pull, Shape::Hook) => "D".to_string(),
-        (Direction::Push, Shape::Slice) => "D".to_string(),
-        (Direction::Straight, Shape::Hook) => "D".to_string(),
-        (Direction::Straight, Shape::Slice) => "D".to_string(),
-    }
-}
-
-fn parse_string(value: &str) -> String {
-    value.trim().trim_matches('"').to_string()
-}
-
-fn normalize_color(input: &str) -> String {
-    let trimmed = input.trim().trim_matches('"');
-    let cleaned = trimmed.trim_start_matches("0x").trim_start_matches('#');
-    format!("0x{}", cleaned.to_uppercase())
-}
-
-fn rank_color_for(rank: &str) -> String {
-    let colors = RANK_COLORS.get_or_init(load_rank_colors);
-    colors
-        .get(rank)
-        .cloned()
-        .unwrap_or_else(|| "0xFFFFFF".to_string())
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-fn load_rank_colors() -> HashMap<String, String> {
-    let mut map = HashMap::new();
-    let data = include_rank_colors!();
-    for line in data.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('[') {
-            continue;
-        }
-        if let Some((key, value)) = trimmed.split_once('=') {
-            let rank_key = parse_string(key.trim());
-            let color_raw = parse_string(value.trim());
-            let color_value = normalize_color(&color_raw);
-            map.insert(rank_key, color_value);
+    #[test]
+    fn test_shot_ids_match_locale_keys() {
+        let locale: toml::Value =
+            toml::from_str(include_str!("../../locales/shots/shots_en.toml")).unwrap();
+        let shots = &locale["shots"]["en"];
+        for id in ShotId::ALL {
+            assert_eq!(serde_json::to_value(id).unwrap(), id.as_str());
+            assert!(shots.get(id.as_str()).is_some(), "missing {}", id.as_str());
+            assert_eq!(ShotId::from_label(id.english_name()), Some(id));
         }
     }
 
-    if map.is_empty() {
-        map.insert("S".to_string(), "0x00B3FF".to_string());
+    #[test]
+    fn test_parts_round_trip() {
+        for direction in Direction::ORDER {
+            for shape in Shape::ORDER {
+                let id = ShotId::from_parts(direction, shape);
+                assert_eq!(id.parts(), Some((direction, shape)));
+            }
+        }
+        assert_eq!(ShotId::DuckHook.parts(), None);
     }
-
-    map
 }
@@ -0,0 +1,495 @@
+use crate::bindings::prepare_input_data;
+use crate::session::Session;
+use crate::{calculate_derived_values_from_input, DerivedValues};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Record layout written by this version of the library
+pub const SHOT_STORE_SCHEMA_VERSION: u32 = 1;
+
+/// One line of a shot store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredShot {
+    pub schema_version: u32,
+
+    /// Sequential within the store, starting at 1
+    pub id: u64,
+
+    pub recorded_at_unix_ms: i64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub player: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub club: Option<String>,
+
+    /// Shot JSON as received, without the "open_golf_coach" object
+    pub input: Value,
+
+    pub derived: DerivedValues,
+}
+
+/// Who hit a shot and when; unset fields come from the shot JSON or the clock
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShotTags {
+    pub player: Option<String>,
+    pub session: Option<String>,
+    pub recorded_at_unix_ms: Option<i64>,
+}
+
+impl ShotTags {
+    /// Tags carried by the shot JSON itself: its "player" and "session" strings
+    pub fn from_shot_json(shot: &Value) -> Self {
+        let text = |key: &str| shot.get(key).and_then(Value::as_str).map(str::to_string);
+        ShotTags {
+            player: text("player"),
+            session: text("session"),
+            recorded_at_unix_ms: None,
+        }
+    }
+}
+
+/// Filter for [`ShotStore::query`]; unset fields match every shot
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShotQuery {
+    /// Inclusive start of the time range
+    pub from_unix_ms: Option<i64>,
+
+    /// Exclusive end of the time range
+    pub to_unix_ms: Option<i64>,
+
+    pub player: Option<String>,
+    pub session: Option<String>,
+    pub club: Option<String>,
+}
+
+impl ShotQuery {
+    pub fn matches(&self, shot: &StoredShot) -> bool {
+        let same =
+            |wanted: &Option<String>, actual: &Option<String>| wanted.is_none() || wanted == actual;
+        self.from_unix_ms
+            .is_none_or(|from| shot.recorded_at_unix_ms >= from)
+            && self
+                .to_unix_ms
+                .is_none_or(|to| shot.recorded_at_unix_ms < to)
+            && same(&self.player, &shot.player)
+            && same(&self.session, &shot.session)
+            && same(&self.club, &shot.club)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// One stored shot per line, same as the store itself
+    JsonLines,
+    /// A single JSON array
+    Json,
+}
+
+/// Error raised by a shot store
+#[derive(Debug)]
+pub enum ShotStoreError {
+    Io(std::io::Error),
+    Parse { line: usize, message: String },
+    UnsupportedVersion { line: usize, version: u32 },
+    Invalid(String),
+}
+
+impl fmt::Display for ShotStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShotStoreError::Io(e) => write!(f, "Shot store I/O error: {}", e),
+            ShotStoreError::Parse { line, message } => {
+                write!(f, "Failed to parse shot store line {}: {}", line, message)
+            }
+            ShotStoreError::UnsupportedVersion { line, version } => write!(
+                f,
+                "Shot store line {} uses schema version {}, newer than supported version {}",
+                line, version, SHOT_STORE_SCHEMA_VERSION
+            ),
+            ShotStoreError::Invalid(msg) => write!(f, "Invalid shot: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ShotStoreError {}
+
+impl From<std::io::Error> for ShotStoreError {
+    fn from(e: std::io::Error) -> Self {
+        ShotStoreError::Io(e)
+    }
+}
+
+/// Append-only JSON Lines file of processed shots
+#[derive(Debug)]
+pub struct ShotStore {
+    path: PathBuf,
+    next_id: u64,
+    read_only: bool,
+}
+
+impl ShotStore {
+    /// Open a store, creating an empty file if there is none.
+    ///
+    /// Every existing line is read once to validate it and continue the ID sequence. A final
+    /// line without its newline, left by a write cut short, is dropped if it does not parse.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ShotStoreError> {
+        let path = path.as_ref().to_path_buf();
+        OpenOptions::new().create(true).append(true).open(&path)?;
+        repair_torn_tail(&path)?;
+        Self::load(path, false)
+    }
+
+    /// Open an existing store for queries and export only.
+    ///
+    /// The file is neither created nor repaired, so it is safe to read while another process
+    /// appends to it; a partial final line is skipped. Appending to this store fails.
+    pub fn open_read_only(path: impl AsRef<Path>) -> Result<Self, ShotStoreError> {
+        Self::load(path.as_ref().to_path_buf(), true)
+    }
+
+    fn load(path: PathBuf, read_only: bool) -> Result<Self, ShotStoreError> {
+        let mut store = ShotStore {
+            path,
+            next_id: 1,
+            read_only,
+        };
+        let last_id = store.read_all()?.iter().map(|shot| shot.id).max();
+        store.next_id = last_id.map_or(1, |id| id + 1);
+        Ok(store)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Calculate a shot from its JSON and append it.
+    ///
+    /// `player` and `session` default to the shot JSON's "player" and "session" strings, the
+    /// club to its "club", and the time to now.
+    pub fn record_json(
+        &mut self,
+        input_json: &str,
+        tags: &ShotTags,
+    ) -> Result<StoredShot, ShotStoreError> {
+        let mut input: Value =
+            serde_json::from_str(input_json).map_err(|e| ShotStoreError::Invalid(e.to_string()))?;
+        let parsed =
+            prepare_input_data(&input).map_err(|e| ShotStoreError::Invalid(e.to_string()))?;
+        let derived = calculate_derived_values_from_input(&parsed);
+        if let Value::Object(map) = &mut input {
+            map.remove("open_golf_coach");
+        }
+
+        let from_json = ShotTags::from_shot_json(&input);
+        let tags = ShotTags {
+            player: tags.player.clone().or(from_json.player),
+            session: tags.session.clone().or(from_json.session),
+            recorded_at_unix_ms: tags.recorded_at_unix_ms,
+        };
+        self.append(input, derived, &tags)
+    }
+
+    /// Append an already calculated shot
+    pub fn append(
+        &mut self,
+        input: Value,
+        derived: DerivedValues,
+        tags: &ShotTags,
+    ) -> Result<StoredShot, ShotStoreError> {
+        if self.read_only {
+            return Err(ShotStoreError::Invalid(
+                "shot store was opened read-only".to_string(),
+            ));
+        }
+        let shot = StoredShot {
+            schema_version: SHOT_STORE_SCHEMA_VERSION,
+            id: self.next_id,
+            recorded_at_unix_ms: tags.recorded_at_unix_ms.unwrap_or_else(now_unix_ms),
+            player: tags.player.clone(),
+            session: tags.session.clone(),
+            club: derived.club.clone(),
+            input,
+            derived,
+        };
+
+        let mut line =
+            serde_json::to_string(&shot).map_err(|e| ShotStoreError::Invalid(e.to_string()))?;
+        line.push('\n');
+        let mut file = OpenOptions::new().append(true).open(&self.path)?;
+        // A crash mid-write can still leave a partial last line; `open` drops it
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+
+        self.next_id += 1;
+        Ok(shot)
+    }
+
+    /// Stored shots matching the query, oldest first
+    pub fn query(&self, query: &ShotQuery) -> Result<Vec<StoredShot>, ShotStoreError> {
+        Ok(self
+            .read_all()?
+            .into_iter()
+            .filter(|shot| query.matches(shot))
+            .collect())
+    }
+
+    /// Matching shots as a [`Session`] for statistics, dispersion and gapping
+    pub fn session(&self, query: &ShotQuery) -> Result<Session, ShotStoreError> {
+        Ok(self
+            .query(query)?
+            .into_iter()
+            .map(|shot| shot.derived)
+            .collect())
+    }
+
+    /// Write the matching shots to `writer`
+    ///
+    /// # Returns
+    /// The number of shots exported
+    pub fn export(
+        &self,
+        query: &ShotQuery,
+        format: ExportFormat,
+        mut writer: impl Write,
+    ) -> Result<usize, ShotStoreError> {
+        let shots = self.query(query)?;
+        let to_invalid = |e: serde_json::Error| ShotStoreError::Invalid(e.to_string());
+        match format {
+            ExportFormat::JsonLines => {
+                for shot in &shots {
+                    serde_json::to_writer(&mut writer, shot).map_err(to_invalid)?;
+                    writer.write_all(b"\n")?;
+                }
+            }
+            ExportFormat::Json => {
+                serde_json::to_writer_pretty(&mut writer, &shots).map_err(to_invalid)?;
+                writer.write_all(b"\n")?;
+            }
+        }
+        writer.flush()?;
+        Ok(shots.len())
+    }
+
+    /// Every record, oldest first; an unparsable final line without a newline is a write
+    /// still in progress (or cut short) and is skipped
+    fn read_all(&self) -> Result<Vec<StoredShot>, ShotStoreError> {
+        let text = std::fs::read_to_string(&self.path)?;
+        let mut shots = Vec::new();
+        for (index, line) in text.split_inclusive('\n').enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match parse_record(line.trim_end_matches(['\n', '\r']), index + 1) {
+                Ok(shot) => shots.push(shot),
+                Err(ShotStoreError::Parse { .. }) if !line.ends_with('\n') => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(shots)
+    }
+}
+
+/// Finish or drop a final line that lacks its newline so the next append starts a fresh line
+fn repair_torn_tail(path: &Path) -> Result<(), ShotStoreError> {
+    let bytes = std::fs::read(path)?;
+    if bytes.is_empty() || bytes.ends_with(b"\n") {
+        return Ok(());
+    }
+    let start = bytes.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+    let tail = String::from_utf8_lossy(&bytes[start..]);
+    let file = OpenOptions::new().append(true).open(path)?;
+    if tail.trim().is_empty() || parse_record(&tail, 0).is_ok() {
+        (&file).write_all(b"\n")?;
+    } else {
+        file.set_len(start as u64)?;
+    }
+    Ok(())
+}
+
+/// Parse one store line, upgrading older schema versions
+fn parse_record(line: &str, line_number: usize) -> Result<StoredShot, ShotStoreError> {
+    let parse_error = |e: serde_json::Error| ShotStoreError::Parse {
+        line: line_number,
+        message: e.to_string(),
+    };
+    let record: Value = serde_json::from_str(line).map_err(parse_error)?;
+    let version = record
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| ShotStoreError::Parse {
+            line: line_number,
+            message: "missing schema_version".to_string(),
+        })?;
+    if version > SHOT_STORE_SCHEMA_VERSION as u64 {
+        return Err(ShotStoreError::UnsupportedVersion {
+            line: line_number,
+            version: version as u32,
+        });
+    }
+    // Version 1 is the first layout; migrations from older versions go here
+    serde_json::from_value(record).map_err(parse_error)
+}
+
+fn now_unix_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "opengolfcoach_{}_{}.jsonl",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    const SHOT: &str = r#"{"ball_speed_meters_per_second": 55.0, "vertical_launch_angle_degrees": 16.0,
+        "total_spin_rpm": 6000.0, "spin_axis_degrees": 2.0, "club": "7i", "player": "sam"}"#;
+
+    #[test]
+    fn test_record_query_and_reopen() {
+        let path = temp_store("query");
+        let mut store = ShotStore::open(&path).unwrap();
+        let at = |ms| ShotTags {
+            recorded_at_unix_ms: Some(ms),
+            session: Some("morning".to_string()),
+            ..Default::default()
+        };
+
+        let first = store.record_json(SHOT, &at(1_000)).unwrap();
+        assert_eq!((first.id, first.club.as_deref()), (1, Some("7i")));
+        assert_eq!(first.player.as_deref(), Some("sam"));
+        assert!(first.derived.carry_distance_meters.unwrap() > 100.0);
+        store
+            .record_json(
+                &SHOT.replace("7i", "PW"),
+                &ShotTags {
+                    player: Some("alex".to_string()),
+                    ..at(2_000)
+                },
+            )
+            .unwrap();
+        store.record_json(SHOT, &at(3_000)).unwrap();
+
+        // IDs continue after reopening
+        let mut store = ShotStore::open(&path).unwrap();
+        assert_eq!(store.record_json(SHOT, &at(4_000)).unwrap().id, 4);
+
+        let query = |query: ShotQuery| store.query(&query).unwrap().len();
+        assert_eq!(query(ShotQuery::default()), 4);
+        assert_eq!(
+            query(ShotQuery {
+                club: Some("7i".to_string()),
+                ..Default::default()
+            }),
+            3
+        );
+        assert_eq!(
+            query(ShotQuery {
+                player: Some("alex".to_string()),
+                ..Default::default()
+            }),
+            1
+        );
+        assert_eq!(
+            query(ShotQuery {
+                from_unix_ms: Some(2_000),
+                to_unix_ms: Some(4_000),
+                ..Default::default()
+            }),
+            2
+        );
+
+        let mut exported = Vec::new();
+        let count = store
+            .export(
+                &ShotQuery {
+                    club: Some("PW".to_string()),
+                    ..Default::default()
+                },
+                ExportFormat::JsonLines,
+                &mut exported,
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+        let line: Value = serde_json::from_slice(&exported).unwrap();
+        assert_eq!(line["input"]["club"], "PW");
+        assert!(line["input"].get("open_golf_coach").is_none());
+
+        assert_eq!(store.session(&ShotQuery::default()).unwrap().len(), 4);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_newer_schema_and_bad_lines() {
+        let path = temp_store("schema");
+        std::fs::write(&path, "{\"schema_version\": 99, \"id\": 1}\n").unwrap();
+        assert!(matches!(
+            ShotStore::open(&path),
+            Err(ShotStoreError::UnsupportedVersion {
+                line: 1,
+                version: 99
+            })
+        ));
+
+        std::fs::write(&path, "\n{not json\n").unwrap();
+        assert!(matches!(
+            ShotStore::open(&path),
+            Err(ShotStoreError::Parse { line: 2, .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_torn_final_line_is_dropped() {
+        let path = temp_store("torn");
+        let mut store = ShotStore::open(&path).unwrap();
+        store.record_json(SHOT, &ShotTags::default()).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"schema_version": 1, "id": 2, "recor"#)
+            .unwrap();
+
+        // Readers skip the partial line and leave it alone; reopening for writing truncates it
+        assert_eq!(store.query(&ShotQuery::default()).unwrap().len(), 1);
+        let before = std::fs::read(&path).unwrap();
+        let mut reader = ShotStore::open_read_only(&path).unwrap();
+        assert_eq!(reader.query(&ShotQuery::default()).unwrap().len(), 1);
+        assert_eq!(std::fs::read(&path).unwrap(), before);
+        assert!(matches!(
+            reader.record_json(SHOT, &ShotTags::default()),
+            Err(ShotStoreError::Invalid(_))
+        ));
+        let mut store = ShotStore::open(&path).unwrap();
+        assert_eq!(store.record_json(SHOT, &ShotTags::default()).unwrap().id, 2);
+        assert_eq!(store.query(&ShotQuery::default()).unwrap().len(), 2);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_read_only_open_does_not_create() {
+        let path = temp_store("missing");
+        assert!(matches!(
+            ShotStore::open_read_only(&path),
+            Err(ShotStoreError::Io(_))
+        ));
+        assert!(!path.exists());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::ball_model::{ball_model, BallModel};
 use crate::Vector3;
 use serde::{Deserialize, Serialize};
 use std::f64::consts::PI;
@@ -142,8 +143,6 @@ fn drag_coefficient(omega: f64, speed: f64, temp_c: f64) -> f64 {
         (0.26 - 0.24 + 0.8 * spin_modifier) * (reynolds - 60000.0) / (240000.0 - 60000.0)
             + 0.24
             + 0.8 * spin_modifier
-    } else if reynolds <= 4000000.0 {
-        (0.30 - 0.26) * (reynolds - 240000.0) / (4000000.0 - 240000.0) + 0.26
     } else {
         (0.30 - 0.26) * (reynolds - 240000.0) / (4000000.0 - 240000.0) + 0.26
     }
@@ -151,6 +150,8 @@ fn drag_coefficient(omega: f64, speed: f64, temp_c: f64) -> f64 {
 
 /// Calculate full ball trajectory using numerical integration
 ///
+/// Uses the active ball model (see [`crate::set_ball_model`]).
+///
 /// Coordinate system: Unreal LEFT HANDED
 /// - X is forward toward target
 /// - Y is right (positive = right) positive sidespin and spin axis is right (fade/slice)
@@ -169,6 +170,7 @@ fn drag_coefficient(omega: f64, speed: f64, temp_c: f64) -> f64 {
 ///
 /// # Returns
 /// Trajectory containing sequences of positions and velocities
+#[allow(clippy::too_many_arguments)]
 pub fn calculate_trajectory(
     ball_speed_mps: f64,
     v_launch_deg: f64,
@@ -179,6 +181,37 @@ pub fn calculate_trajectory(
     temperature_k: f64,
     humidity_percent: f64,
     pressure_pa: Option<f64>,
+) -> Trajectory {
+    calculate_trajectory_with_model(
+        ball_speed_mps,
+        v_launch_deg,
+        h_launch_deg,
+        backspin_rpm,
+        sidespin_rpm,
+        elevation_m,
+        temperature_k,
+        humidity_percent,
+        pressure_pa,
+        &ball_model(),
+    )
+}
+
+/// Calculate full ball trajectory with an explicit ball model
+///
+/// Same as [`calculate_trajectory`], but the drag and lift coefficients are scaled and the
+/// spin decays according to `model` instead of the active ball model.
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_trajectory_with_model(
+    ball_speed_mps: f64,
+    v_launch_deg: f64,
+    h_launch_deg: f64,
+    backspin_rpm: f64,
+    sidespin_rpm: f64,
+    elevation_m: f64,
+    temperature_k: f64,
+    humidity_percent: f64,
+    pressure_pa: Option<f64>,
+    model: &BallModel,
 ) -> Trajectory {
     // Convert launch angles to radians
     let v_launch_rad = v_launch_deg * PI / 180.0;
@@ -217,7 +250,7 @@ pub fn calculate_trajectory(
 
     // Spin decay rate
     // Fraction per second, exponential decay
-    const SPIN_DECAY_RATE: f64 = 0.04; // 4% per second
+    let spin_decay_rate = model.spin_decay_rate;
 
     // Simulate until ball hits ground (z <= 0) or is falling (vz < 0)
     // Need at least one iteration to start
@@ -246,8 +279,9 @@ pub fn calculate_trajectory(
         let current_speed = velocity.magnitude();
 
         // Calculate aerodynamic coefficients
-        let lift_coeff = lift_coefficient(total_spin, current_speed);
-        let drag_coeff = drag_coefficient(total_spin, current_speed, temperature_c);
+        let lift_coeff = lift_coefficient(total_spin, current_speed) * model.lift_scale;
+        let drag_coeff =
+            drag_coefficient(total_spin, current_speed, temperature_c) * model.drag_scale;
 
         // Calculate forces
         let dynamic_pressure = 0.5 * air_density * cross_sectional_area * current_speed.powi(2);
@@ -322,7 +356,7 @@ pub fn calculate_trajectory(
         velocity = new_velocity;
 
         // Apply spin decay
-        total_spin *= (-SPIN_DECAY_RATE * DELTA_TIME).exp();
+        total_spin *= (-spin_decay_rate * DELTA_TIME).exp();
 
         // Update time
         time += DELTA_TIME;
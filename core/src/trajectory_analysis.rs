@@ -39,7 +39,7 @@ pub fn get_apex_position(trajectory: &Trajectory) -> Vector3 {
     trajectory
         .points
         .iter()
-        .max_by(|a, b| a.z.partial_cmp(&b.z).unwrap())
+        .max_by(|a, b| a.z.total_cmp(&b.z))
         .map(|p| p.position())
         .unwrap_or(Vector3::new(f64::NAN, f64::NAN, f64::NAN))
 }
@@ -50,7 +50,7 @@ pub fn get_time_to_apex(trajectory: &Trajectory) -> f64 {
     trajectory
         .points
         .iter()
-        .max_by(|a, b| a.z.partial_cmp(&b.z).unwrap())
+        .max_by(|a, b| a.z.total_cmp(&b.z))
         .map(|p| p.t)
         .unwrap_or(f64::NAN)
 }
@@ -62,7 +62,7 @@ pub fn get_peak_height(trajectory: &Trajectory) -> f64 {
         .points
         .iter()
         .map(|p| p.z)
-        .max_by(|a, b| a.partial_cmp(b).unwrap())
+        .max_by(|a, b| a.total_cmp(b))
         .unwrap_or(f64::NAN)
 }
 
@@ -89,16 +89,26 @@ pub fn get_offline_distance(trajectory: &Trajectory) -> f64 {
     landing_pos.y
 }
 
+/// Get offline distance measured from a target line
+/// `target_bearing_deg` is the direction of the target line (positive = right of the 0° line)
+pub fn get_offline_distance_from_target_line(
+    trajectory: &Trajectory,
+    target_bearing_deg: f64,
+) -> f64 {
+    let landing_pos = get_landing_position(trajectory);
+    let (s, c) = (target_bearing_deg * PI / 180.0).sin_cos();
+    landing_pos.y * c - landing_pos.x * s
+}
+
 /// Estimate run-out after landing and return total distance (carry + roll)
 pub fn get_total_distance(trajectory: &Trajectory) -> f64 {
-    let carry = get_carry_distance(trajectory);
     let landing_pos = get_landing_position(trajectory);
     let landing_vel = get_landing_velocity(trajectory);
 
     // Horizontal speed drives roll potential.
     let horizontal_speed = (landing_vel.x.powi(2) + landing_vel.y.powi(2)).sqrt();
     if horizontal_speed <= 0.1 {
-        return carry;
+        return get_carry_distance(trajectory);
     }
 
     // Shallow descent angles roll more; steep wedge shots stop quickly.
@@ -110,15 +120,41 @@ pub fn get_total_distance(trajectory: &Trajectory) -> f64 {
     let mut roll_distance = base_roll * descent_factor * ROLL_EFFICIENCY * ROLL_SCALING_COEFF;
     roll_distance = roll_distance.max(0.0);
 
-    // Roll follows the down-range heading inferred from horizontal landing velocity,
-    // or fall back to carry direction if velocity is ill-defined.
+    let heading = roll_heading(&landing_pos, &landing_vel);
+    let roll_vector = Vector3::new(heading.x * roll_distance, heading.y * roll_distance, 0.0);
+    let total_vector = landing_pos.add(&roll_vector);
+    total_vector.magnitude()
+}
+
+/// Roll follows the down-range heading inferred from horizontal landing velocity,
+/// or falls back to carry direction if velocity is ill-defined.
+fn roll_heading(landing_pos: &Vector3, landing_vel: &Vector3) -> Vector3 {
     let mut heading = Vector3::new(landing_vel.x, landing_vel.y, 0.0);
     if heading.magnitude() <= 0.01 {
         heading = Vector3::new(landing_pos.x, landing_pos.y, 0.0);
     }
-    let heading = heading.normalize();
+    heading.normalize()
+}
 
-    let roll_vector = Vector3::new(heading.x * roll_distance, heading.y * roll_distance, 0.0);
-    let total_vector = landing_pos.add(&roll_vector);
-    total_vector.magnitude()
+/// Where the ball came to rest: the landing position moved along the roll heading until it
+/// is `total_distance_m` from the tee
+pub fn get_resting_position(
+    landing_pos: &Vector3,
+    landing_vel: &Vector3,
+    total_distance_m: f64,
+) -> Vector3 {
+    let heading = roll_heading(landing_pos, landing_vel);
+    let landing = Vector3::new(landing_pos.x, landing_pos.y, 0.0);
+    // Solve |landing + roll * heading| = total for the forward root
+    let along = landing.dot(&heading);
+    let discriminant = along.powi(2) - landing.magnitude().powi(2) + total_distance_m.powi(2);
+    let roll = (-along + discriminant.max(0.0).sqrt()).max(0.0);
+    if !roll.is_finite() {
+        return landing;
+    }
+    Vector3::new(
+        landing.x + heading.x * roll,
+        landing.y + heading.y * roll,
+        0.0,
+    )
 }
@@ -0,0 +1,488 @@
+use crate::session::{Session, SessionMetric};
+use serde::{Deserialize, Serialize};
+
+/// Settings for [`analyze_trends`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrendOptions {
+    /// Metrics to check for drift
+    pub metrics: Vec<SessionMetric>,
+
+    /// Length of the window slid across each club's shots; the trend is fitted per window
+    pub window_shots: usize,
+
+    /// Clubs with fewer shots with the metric are not checked
+    pub min_shots: usize,
+
+    /// Largest two-sided p-value of the regression slope still reported
+    pub significance: f64,
+
+    /// Smaller fitted changes are not reported, however significant
+    pub min_change_percent: f64,
+}
+
+impl Default for TrendOptions {
+    fn default() -> Self {
+        TrendOptions {
+            metrics: vec![
+                SessionMetric::ClubSpeedMetersPerSecond,
+                SessionMetric::BallSpeedMetersPerSecond,
+                SessionMetric::SmashFactor,
+                SessionMetric::CarryDistanceMeters,
+            ],
+            window_shots: 20,
+            min_shots: 8,
+            significance: 0.05,
+            min_change_percent: 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendDirection {
+    Increasing,
+    Decreasing,
+}
+
+/// A significant drift in one metric over a stretch of a club's shots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrendFinding {
+    pub metric: SessionMetric,
+
+    /// Club label as given in the input; `None` for shots without one
+    pub club: Option<String>,
+    pub direction: TrendDirection,
+
+    /// Shots in the window, and the indices in [`Session::shots`] of its first and last shot
+    pub shot_count: usize,
+    pub first_shot_index: usize,
+    pub last_shot_index: usize,
+
+    /// Values of the fitted line at the first and last shot of the window
+    pub start_value: f64,
+    pub end_value: f64,
+
+    /// Change relative to `start_value`; absent when that is zero
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_percent: Option<f64>,
+
+    pub slope_per_shot: f64,
+
+    /// Two-sided p-value of the slope (Student's t with n - 2 degrees of freedom), multiplied
+    /// by the number of window positions tested (Bonferroni) and capped at 1
+    pub p_value: f64,
+
+    /// E.g. "7i: club speed dropped 4.1% over the last 20 shots" or, for a change earlier in
+    /// the session, "7i: club speed dropped 4.1% over 7i shots 11-30" (counting that club's
+    /// shots only)
+    pub message: String,
+}
+
+/// Least-squares line through evenly spaced values
+struct LinearFit {
+    intercept: f64,
+    slope: f64,
+    p_value: f64,
+}
+
+/// Fit `values[i] = intercept + slope * i` and test the slope against zero
+fn fit_line(values: &[f64]) -> Option<LinearFit> {
+    let n = values.len();
+    if n < 3 {
+        return None;
+    }
+    let n_f = n as f64;
+    let mean_x = (n_f - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n_f;
+    let (mut sxx, mut sxy) = (0.0, 0.0);
+    for (i, y) in values.iter().enumerate() {
+        let dx = i as f64 - mean_x;
+        sxx += dx * dx;
+        sxy += dx * (y - mean_y);
+    }
+    let slope = sxy / sxx;
+    let intercept = mean_y - slope * mean_x;
+    let residual_ss: f64 = values
+        .iter()
+        .enumerate()
+        .map(|(i, y)| (y - intercept - slope * i as f64).powi(2))
+        .sum();
+    let df = n_f - 2.0;
+    let standard_error = (residual_ss / df / sxx).sqrt();
+
+    let p_value = if standard_error > 0.0 {
+        student_t_two_sided_p(slope / standard_error, df)
+    } else if slope != 0.0 {
+        0.0 // A perfect line
+    } else {
+        1.0
+    };
+    Some(LinearFit {
+        intercept,
+        slope,
+        p_value,
+    })
+}
+
+/// Two-sided p-value of a t statistic: I_{df / (df + t²)}(df / 2, 1 / 2)
+fn student_t_two_sided_p(t: f64, df: f64) -> f64 {
+    regularized_incomplete_beta(df / (df + t * t), df / 2.0, 0.5)
+}
+
+/// I_x(a, b) via its continued fraction (Numerical Recipes, `betai`)
+fn regularized_incomplete_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(x, a, b) / a
+    } else {
+        1.0 - front * beta_continued_fraction(1.0 - x, b, a) / b
+    }
+}
+
+fn beta_continued_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 200;
+    const EPSILON: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        for numerator in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = 1.0 + numerator / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            h *= d * c;
+        }
+        if (d * c - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    h
+}
+
+/// Lanczos approximation (g = 7, n = 9) of ln Γ(x) for x > 0
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| {
+            sum + c / (x + i as f64 + 1.0)
+        });
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// Words used for a metric in finding messages
+fn metric_label(metric: SessionMetric) -> &'static str {
+    match metric {
+        SessionMetric::CarryDistanceMeters => "carry",
+        SessionMetric::TotalDistanceMeters => "total distance",
+        SessionMetric::OfflineDistanceMeters => "offline distance",
+        SessionMetric::BallSpeedMetersPerSecond => "ball speed",
+        SessionMetric::ClubSpeedMetersPerSecond => "club speed",
+        SessionMetric::SmashFactor => "smash factor",
+        SessionMetric::TotalSpinRpm => "spin",
+        SessionMetric::VerticalLaunchAngleDegrees => "launch angle",
+    }
+}
+
+/// Significant drifts (fatigue or improvement) within each club's shots.
+///
+/// Per club and metric, a window of `window_shots` values is slid across the shots in the
+/// order they were hit and a line is fitted in each position. A window qualifies when its slope
+/// is significant at `significance`, after a Bonferroni correction for the number of positions,
+/// and the fitted change is at least `min_change_percent`. A drift in the latest window is
+/// reported as still under way; otherwise the largest qualifying change is reported with where
+/// it happened. Clubs are checked separately
+/// so a club change is not mistaken for a trend. Largest changes come first.
+pub fn analyze_trends(session: &Session, options: &TrendOptions) -> Vec<TrendFinding> {
+    let mut findings = Vec::new();
+    for club in session.clubs() {
+        for &metric in &options.metrics {
+            let series: Vec<(usize, f64)> = session
+                .shots()
+                .iter()
+                .enumerate()
+                .filter(|(_, shot)| shot.club == club)
+                .filter_map(|(i, shot)| Some((i, metric.value(shot)?)))
+                .collect();
+            if series.len() < options.min_shots.max(3) {
+                continue;
+            }
+            let window_len = options.window_shots.max(3).min(series.len());
+            let last_start = series.len() - window_len;
+            let in_window = |start: usize| {
+                let window = &series[start..start + window_len];
+                trend_in_window(metric, club.clone(), window, start, last_start + 1, options)
+            };
+            // A drift still under way wins; otherwise report where the largest one happened
+            let strongest = in_window(last_start).or_else(|| {
+                (0..last_start)
+                    .filter_map(in_window)
+                    .max_by(|a, b| change_size(a).total_cmp(&change_size(b)))
+            });
+            if let Some(finding) = strongest {
+                findings.push(finding);
+            }
+        }
+    }
+    findings.sort_by(|a, b| change_size(b).total_cmp(&change_size(a)));
+    findings
+}
+
+fn change_size(finding: &TrendFinding) -> f64 {
+    finding.change_percent.map_or(0.0, f64::abs)
+}
+
+/// Trend in the window starting at the club's `start`-th shot, one of `window_count` positions
+fn trend_in_window(
+    metric: SessionMetric,
+    club: Option<String>,
+    window: &[(usize, f64)],
+    start: usize,
+    window_count: usize,
+    options: &TrendOptions,
+) -> Option<TrendFinding> {
+    let values: Vec<f64> = window.iter().map(|&(_, value)| value).collect();
+    let fit = fit_line(&values)?;
+    let p_value = (fit.p_value * window_count as f64).min(1.0);
+    if p_value > options.significance || fit.slope == 0.0 {
+        return None;
+    }
+
+    let shot_count = values.len();
+    let start_value = fit.intercept;
+    let end_value = fit.intercept + fit.slope * (shot_count - 1) as f64;
+    let change_percent = (start_value.abs() > f64::EPSILON)
+        .then(|| (end_value - start_value) / start_value.abs() * 100.0);
+    if change_percent.is_some_and(|percent| percent.abs() < options.min_change_percent) {
+        return None;
+    }
+
+    let direction = if fit.slope > 0.0 {
+        TrendDirection::Increasing
+    } else {
+        TrendDirection::Decreasing
+    };
+    let verb = match direction {
+        TrendDirection::Increasing => "rose",
+        TrendDirection::Decreasing => "dropped",
+    };
+    let amount = match change_percent {
+        Some(percent) => format!("{:.1}%", percent.abs()),
+        None => format!("by {:.1}", (end_value - start_value).abs()),
+    };
+    let prefix = club
+        .as_deref()
+        .map(|c| format!("{}: ", c))
+        .unwrap_or_default();
+    let span = if start + 1 == window_count {
+        format!("the last {} shots", shot_count)
+    } else {
+        format!(
+            "{} shots {}-{}",
+            club.as_deref().unwrap_or("untagged"),
+            start + 1,
+            start + shot_count
+        )
+    };
+    let message = format!(
+        "{}{} {} {} over {}",
+        prefix,
+        metric_label(metric),
+        verb,
+        amount,
+        span
+    );
+
+    Some(TrendFinding {
+        metric,
+        club,
+        direction,
+        shot_count,
+        first_shot_index: window[0].0,
+        last_shot_index: window[shot_count - 1].0,
+        start_value,
+        end_value,
+        change_percent,
+        slope_per_shot: fit.slope,
+        p_value,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DerivedValues;
+
+    /// Deterministic jitter in [-1, 1)
+    fn jitter(i: usize) -> f64 {
+        ((i * 7919 + 13) % 101) as f64 / 50.5 - 1.0
+    }
+
+    fn shot(club: &str, club_speed: f64, carry: f64) -> DerivedValues {
+        let mut shot = DerivedValues::new();
+        shot.club = Some(club.to_string());
+        shot.club_speed_meters_per_second = Some(club_speed);
+        shot.carry_distance_meters = Some(carry);
+        shot
+    }
+
+    #[test]
+    fn test_t_distribution_p_values() {
+        // Two-sided 5% critical values
+        assert!((student_t_two_sided_p(2.306, 8.0) - 0.05).abs() < 1e-3);
+        assert!((student_t_two_sided_p(2.101, 18.0) - 0.05).abs() < 1e-3);
+        assert!((student_t_two_sided_p(0.0, 10.0) - 1.0).abs() < 1e-12);
+        assert!((ln_gamma(5.0) - 24.0_f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fatigue_detected_in_recent_window() {
+        // 10 steady driver swings, then 20 that lose 0.1 m/s each
+        let mut session: Session = (0..10)
+            .map(|i| {
+                shot(
+                    "Driver",
+                    45.0 + 0.3 * jitter(i),
+                    220.0 + 3.0 * jitter(i + 50),
+                )
+            })
+            .collect();
+        session.extend((0..20).map(|i| {
+            let speed = 45.0 - 0.1 * i as f64 + 0.3 * jitter(i + 10);
+            shot("Driver", speed, 220.0 + 3.0 * jitter(i + 70))
+        }));
+        // Another club in between must not break the driver's sequence
+        session.add(shot("7i", 38.0, 140.0));
+
+        let findings = analyze_trends(&session, &TrendOptions::default());
+        assert_eq!(findings.len(), 1, "{:?}", findings);
+        let finding = &findings[0];
+        assert_eq!(finding.metric, SessionMetric::ClubSpeedMetersPerSecond);
+        assert_eq!(finding.direction, TrendDirection::Decreasing);
+        assert_eq!((finding.shot_count, finding.first_shot_index), (20, 10));
+        let percent = finding.change_percent.unwrap();
+        assert!((percent + 4.2).abs() < 0.6, "{}", percent);
+        assert!(finding.p_value < 1e-6);
+        assert_eq!(
+            finding.message,
+            format!(
+                "Driver: club speed dropped {:.1}% over the last 20 shots",
+                -percent
+            )
+        );
+    }
+
+    #[test]
+    fn test_dip_located_in_middle_of_session() {
+        // 10 steady swings, 20 that lose 0.1 m/s each, then 15 steady at the lower speed
+        let speeds = (0..10)
+            .map(|_| 45.0)
+            .chain((0..20).map(|i| 45.0 - 0.1 * i as f64))
+            .chain((0..15).map(|_| 43.1));
+        // A 7i warm-up swing first, so session indices run one ahead of the driver's shots
+        let session: Session = std::iter::once(shot("7i", 38.0, 140.0))
+            .chain(
+                speeds
+                    .enumerate()
+                    .map(|(i, speed)| shot("Driver", speed + 0.3 * jitter(i), 220.0)),
+            )
+            .collect();
+
+        let findings = analyze_trends(&session, &TrendOptions::default());
+        assert_eq!(findings.len(), 1, "{:?}", findings);
+        let finding = &findings[0];
+        assert_eq!(finding.direction, TrendDirection::Decreasing);
+        // The drift spans session shots 11-30; noise may shift the best window by a shot
+        assert!(finding.first_shot_index.abs_diff(11) <= 1, "{:?}", finding);
+        assert_eq!(finding.last_shot_index, finding.first_shot_index + 19);
+        // The message counts driver shots, from 1
+        assert!(
+            finding.message.ends_with(&format!(
+                "over Driver shots {}-{}",
+                finding.first_shot_index, finding.last_shot_index
+            )),
+            "{}",
+            finding.message
+        );
+    }
+
+    #[test]
+    fn test_long_noisy_session_has_no_findings() {
+        // Many overlapping windows over pure noise must not add up to a false alarm
+        let mut state: u64 = 12_345;
+        let mut noise = move || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+        };
+        let session: Session = (0..150)
+            .map(|_| shot("7i", 38.0 + 1.5 * noise(), 140.0 + 8.0 * noise()))
+            .collect();
+        assert_eq!(analyze_trends(&session, &TrendOptions::default()), vec![]);
+    }
+
+    #[test]
+    fn test_noise_and_small_changes_ignored() {
+        let noisy: Session = (0..30)
+            .map(|i| shot("7i", 38.0 + jitter(i), 140.0 + 5.0 * jitter(i + 30)))
+            .collect();
+        assert!(analyze_trends(&noisy, &TrendOptions::default()).is_empty());
+
+        // Perfectly steady improvement of 1% is significant but below min_change_percent
+        let slow: Session = (0..20)
+            .map(|i| shot("7i", 38.0 + 0.02 * i as f64, 140.0))
+            .collect();
+        assert!(analyze_trends(&slow, &TrendOptions::default()).is_empty());
+        let options = TrendOptions {
+            min_change_percent: 0.5,
+            ..Default::default()
+        };
+        let findings = analyze_trends(&slow, &options);
+        assert_eq!(findings[0].direction, TrendDirection::Increasing);
+        assert!(findings[0].message.contains("club speed rose 1.0%"));
+    }
+}
@@ -26,7 +26,6 @@ impl Vector3 {
         }
     }
 
-    #[allow(dead_code)]
     pub fn dot(&self, other: &Vector3) -> f64 {
         self.x * other.x + self.y * other.y + self.z * other.z
     }
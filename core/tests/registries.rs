@@ -0,0 +1,216 @@
+//! Process-wide settings: each test replaces one, observes the change and restores the default.
+//!
+//! They live in their own test binary so the library's unit tests never see an override, and
+//! hold `GLOBALS` so they do not see each other's.
+
+use opengolfcoach::{
+    ball_model, benchmarks, calculate_derived_values, classifier_config, color_theme,
+    color_theme_names, compare_to_benchmark, default_benchmarks, default_color_theme,
+    default_impact_bands, default_reference_library, impact_bands, load_rank_schemes_from_str,
+    load_reference_library_from_str, rank_scheme_names, reference_library, register_color_theme,
+    reset_ball_model, reset_benchmarks, reset_classifier_config, reset_color_themes,
+    reset_impact_bands, reset_rank_schemes, reset_reference_library, set_ball_model,
+    set_benchmarks, set_classifier_config, set_impact_bands, set_reference_library, BallModel,
+    ClassifierConfig, DerivedValues, SkillLevel,
+};
+use serde_json::Value;
+use std::sync::{Mutex, MutexGuard};
+
+static GLOBALS: Mutex<()> = Mutex::new(());
+
+fn lock_globals() -> MutexGuard<'static, ()> {
+    GLOBALS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Derived values for a 65 m/s drive started `hla` degrees right, plus any extra
+/// comma-separated fields
+fn derive_at(hla: f64, extra: &str) -> Value {
+    let json_input = format!(
+        r#"{{"ball_speed_meters_per_second": 65.0, "vertical_launch_angle_degrees": 12.0,
+            "horizontal_launch_angle_degrees": {}, "total_spin_rpm": 3000.0,
+            "spin_axis_degrees": 0.0{}}}"#,
+        hla, extra
+    );
+    let output: Value = serde_json::from_str(&calculate_derived_values(&json_input).unwrap())
+        .expect("output is JSON");
+    output["open_golf_coach"].clone()
+}
+
+fn derive(extra: &str) -> Value {
+    derive_at(0.0, extra)
+}
+
+#[test]
+fn test_impact_bands_override_and_reset() {
+    let _guard = lock_globals();
+    let club_speed = || derive("")["club_speed_meters_per_second"].as_f64().unwrap();
+    let default_speed = club_speed();
+
+    let mut bands = default_impact_bands();
+    for band in &mut bands {
+        band.base_cor -= 0.05;
+    }
+    set_impact_bands(bands.clone()).unwrap();
+    assert_eq!(impact_bands(), bands);
+    // A less springy face needs more club speed for the same ball speed
+    assert!(club_speed() > default_speed);
+
+    bands[0].base_cor = 2.0;
+    assert!(set_impact_bands(bands).is_err());
+
+    reset_impact_bands();
+    assert_eq!(impact_bands(), default_impact_bands());
+    assert_eq!(club_speed(), default_speed);
+}
+
+#[test]
+fn test_ball_model_override_and_reset() {
+    let _guard = lock_globals();
+    let carry = || derive("")["carry_distance_meters"].as_f64().unwrap();
+    let default_carry = carry();
+
+    let draggy = BallModel {
+        drag_scale: 1.2,
+        ..BallModel::default()
+    };
+    set_ball_model(draggy).unwrap();
+    assert_eq!(ball_model(), draggy);
+    assert!(carry() < default_carry);
+
+    assert!(set_ball_model(BallModel {
+        drag_scale: -1.0,
+        ..BallModel::default()
+    })
+    .is_err());
+    assert_eq!(ball_model(), draggy);
+
+    reset_ball_model();
+    assert_eq!(ball_model(), BallModel::default());
+    assert_eq!(carry(), default_carry);
+}
+
+#[test]
+fn test_classifier_config_override_and_reset() {
+    let _guard = lock_globals();
+    let default_config = classifier_config();
+    let shot_name = || {
+        derive_at(4.0, "")["shot_name"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(shot_name(), "Push");
+
+    let beginner = ClassifierConfig::for_skill_level(SkillLevel::Beginner);
+    set_classifier_config(beginner).unwrap();
+    assert_eq!(classifier_config(), beginner);
+    assert_eq!(shot_name(), "Straight");
+
+    assert!(set_classifier_config(ClassifierConfig {
+        straight_max_hla_degrees: -1.0,
+        ..beginner
+    })
+    .is_err());
+    assert_eq!(classifier_config(), beginner);
+
+    reset_classifier_config();
+    assert_eq!(classifier_config(), default_config);
+    assert_eq!(shot_name(), "Push");
+}
+
+#[test]
+fn test_rank_schemes_and_color_themes_register_and_reset() {
+    let _guard = lock_globals();
+    let rank = |extra: &str| derive(extra)["shot_rank"].as_str().unwrap().to_string();
+    assert_eq!(rank(""), "B");
+
+    let names = load_rank_schemes_from_str(
+        r#"default_scheme = "league"
+
+        [schemes.league]
+        pull = { hook = "E", draw = "E", none = "E", fade = "E", slice = "E" }
+        straight = { hook = "E", draw = "E", none = "S", fade = "E", slice = "E" }
+        push = { hook = "E", draw = "E", none = "E", fade = "E", slice = "E" }
+        "#,
+    )
+    .unwrap();
+    assert_eq!(names, ["league"]);
+    assert!(rank_scheme_names().contains(&"league".to_string()));
+    assert_eq!(rank(""), "S");
+    assert_eq!(rank(r#", "rank_scheme": "beginner""#), "B");
+
+    let mut theme = default_color_theme();
+    theme.colors.insert("S".to_string(), "0x123456".to_string());
+    register_color_theme("club", theme.clone()).unwrap();
+    assert_eq!(color_theme("club"), Some(theme));
+    assert_eq!(
+        derive(r#", "color_theme": "club""#)["shot_color_rgb"],
+        "0x123456"
+    );
+
+    reset_rank_schemes();
+    reset_color_themes();
+    assert!(!rank_scheme_names().contains(&"league".to_string()));
+    assert!(!color_theme_names().contains(&"club".to_string()));
+    assert_eq!(rank(""), "B");
+}
+
+#[test]
+fn test_reference_library_override_and_reset() {
+    let _guard = lock_globals();
+    let label = || {
+        derive(r#", "shot_classifier": "nearest_neighbor""#)["reference_match"]["label"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(label(), "straight");
+
+    let library = load_reference_library_from_str(
+        r#"[[shots]]
+        label = "stock_drive"
+        name = "Stock Drive"
+        rank = "S"
+        features = { horizontal_launch_angle_degrees = 0.0, spin_axis_degrees = 0.0 }
+        "#,
+    )
+    .unwrap();
+    set_reference_library(library.clone()).unwrap();
+    assert_eq!(reference_library(), library);
+    assert_eq!(label(), "stock_drive");
+
+    let mut empty = library;
+    empty.shots.clear();
+    assert!(set_reference_library(empty).is_err());
+
+    reset_reference_library();
+    assert_eq!(reference_library(), default_reference_library());
+    assert_eq!(label(), "straight");
+}
+
+#[test]
+fn test_benchmarks_override_and_reset() {
+    let _guard = lock_globals();
+    let shot: DerivedValues = serde_json::from_value(derive(r#", "club": "7i""#)).unwrap();
+    let band = || {
+        compare_to_benchmark(std::slice::from_ref(&shot), None, None)
+            .unwrap()
+            .band
+    };
+    assert_eq!(band(), "mid_handicap");
+
+    let mut tables = default_benchmarks();
+    tables.default_band = "tour".to_string();
+    set_benchmarks(tables.clone()).unwrap();
+    assert_eq!(benchmarks(), tables);
+    assert_eq!(band(), "tour");
+
+    tables.default_band = "no_such_band".to_string();
+    assert!(set_benchmarks(tables).is_err());
+
+    reset_benchmarks();
+    assert_eq!(benchmarks(), default_benchmarks());
+    assert_eq!(band(), "mid_handicap");
+}
@@ -1,10 +1,31 @@
 use opengolfcoach::bindings::calculate_derived_values;
+use opengolfcoach::{DerivedValues, ExportFormat, ShotQuery, ShotStore, ShotTags};
 use std::io::{BufRead, BufReader, Write};
 use std::net::{TcpListener, TcpStream};
 
 const SERVER_PORT: u16 = 10000;
 
-fn handle_client(mut stream: TcpStream) -> std::io::Result<()> {
+const USAGE: &str = "Usage:
+  server [--store <shots.jsonl>]
+  server export <shots.jsonl> [--player P] [--session S] [--club C] [--from MS] [--to MS] [--json]";
+
+/// Append a calculated shot (the response JSON) to the store
+fn store_shot(store: &mut ShotStore, result_json: &str) -> Result<(), String> {
+    let mut shot: serde_json::Value =
+        serde_json::from_str(result_json).map_err(|e| e.to_string())?;
+    let derived = shot
+        .as_object_mut()
+        .and_then(|map| map.remove("open_golf_coach"))
+        .ok_or("missing open_golf_coach")?;
+    let derived: DerivedValues = serde_json::from_value(derived).map_err(|e| e.to_string())?;
+    let tags = ShotTags::from_shot_json(&shot);
+    store
+        .append(shot, derived, &tags)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+fn handle_client(mut stream: TcpStream, store: Option<&mut ShotStore>) -> std::io::Result<()> {
     let peer_addr = stream.peer_addr()?;
     println!("New connection from: {}", peer_addr);
 
@@ -41,8 +62,14 @@ fn handle_client(mut stream: TcpStream) -> std::io::Result<()> {
     }
 
     // Process the golf shot calculation
-    match calculate_derived_values(&input_json.trim()) {
+    match calculate_derived_values(input_json.trim()) {
         Ok(result_json) => {
+            if let Some(store) = store {
+                if let Err(e) = store_shot(store, &result_json) {
+                    eprintln!("Failed to store shot from {}: {}", peer_addr, e);
+                }
+            }
+
             // Compact the JSON (remove pretty-printing newlines) to ensure line-delimited protocol
             let compacted = match serde_json::from_str::<serde_json::Value>(&result_json) {
                 Ok(json) => serde_json::to_string(&json).unwrap_or(result_json),
@@ -67,20 +94,81 @@ fn handle_client(mut stream: TcpStream) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Print the stored shots matching the filter arguments to stdout
+fn export(args: &[String]) -> Result<(), String> {
+    let (path, filters) = args.split_first().ok_or(USAGE)?;
+    let mut query = ShotQuery::default();
+    let mut format = ExportFormat::JsonLines;
+    let mut filters = filters.iter();
+    while let Some(flag) = filters.next() {
+        if flag == "--json" {
+            format = ExportFormat::Json;
+            continue;
+        }
+        let value = filters.next().ok_or(USAGE)?;
+        let time = || value.parse::<i64>().map_err(|e| format!("{}: {}", flag, e));
+        match flag.as_str() {
+            "--player" => query.player = Some(value.clone()),
+            "--session" => query.session = Some(value.clone()),
+            "--club" => query.club = Some(value.clone()),
+            "--from" => query.from_unix_ms = Some(time()?),
+            "--to" => query.to_unix_ms = Some(time()?),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let store = ShotStore::open_read_only(path).map_err(|e| e.to_string())?;
+    let count = store
+        .export(&query, format, std::io::stdout().lock())
+        .map_err(|e| e.to_string())?;
+    eprintln!("Exported {} shots", count);
+    Ok(())
+}
+
 fn main() -> std::io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut store = match args.first().map(String::as_str) {
+        Some("export") => {
+            if let Err(e) = export(&args[1..]) {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+            return Ok(());
+        }
+        Some("--store") => match args.get(1).map(ShotStore::open) {
+            Some(Ok(store)) => Some(store),
+            Some(Err(e)) => {
+                eprintln!("Cannot open shot store: {}", e);
+                std::process::exit(1);
+            }
+            None => {
+                eprintln!("{}", USAGE);
+                std::process::exit(2);
+            }
+        },
+        Some(_) => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+        None => None,
+    };
+
     let bind_addr = format!("127.0.0.1:{}", SERVER_PORT);
     let listener = TcpListener::bind(&bind_addr)?;
 
     println!("OpenGolfCoach TCP Server");
     println!("========================");
     println!("Listening on {}", bind_addr);
+    if let Some(store) = &store {
+        println!("Storing shots in {}", store.path().display());
+    }
     println!("Waiting for connections...\n");
 
     for stream in listener.incoming() {
         match stream {
             Ok(stream) => {
                 // Handle each client connection
-                if let Err(e) = handle_client(stream) {
+                if let Err(e) = handle_client(stream, store.as_mut()) {
                     eprintln!("Error handling client: {}", e);
                 }
             }
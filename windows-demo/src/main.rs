@@ -35,7 +35,7 @@ struct DefinitionsEn {
 fn load_shot_tooltips() -> HashMap<String, String> {
     let toml_str = include_str!("../../locales/shots/shots_en.toml");
 
-    // The structure is: [shots.en.shot_name]
+    // The structure is: [shots.en.shot_id]
     #[derive(Deserialize)]
     struct ShotsFile {
         shots: ShotsContainer,
@@ -94,6 +94,7 @@ fn load_definition_tooltips() -> HashMap<String, String> {
 
 #[derive(Clone, Debug)]
 struct ShotResult {
+    shot_id: String,
     shot_name: String,
     shot_rank: String,
     shot_color_rgb: String,
@@ -108,6 +109,7 @@ struct ShotResult {
     // Club data
     club_speed_mph: Option<f64>,
     smash_factor: Option<f64>,
+    #[allow(dead_code)]
     optimal_maximum_distance_meters: Option<f64>,
     distance_efficiency_percent: Option<f64>,
     club_path_degrees: Option<f64>,
@@ -118,6 +120,7 @@ struct ShotResult {
 impl Default for ShotResult {
     fn default() -> Self {
         Self {
+            shot_id: "".to_string(),
             shot_name: "".to_string(),
             shot_rank: "".to_string(),
             shot_color_rgb: "0xFFFFFF".to_string(), // White default
@@ -292,9 +295,9 @@ impl OpenGolfCoachApp {
                 continue; // Continue to next shot instead of closing connection
             }
 
-            // Apply sign inversion for left-handed golfers before processing
+            // Tell the core library about left-handed golfers before processing
             let processed_json = if is_left_handed.load(Ordering::Relaxed) {
-                invert_signs_for_left_handed(input_json.trim())
+                mark_left_handed(input_json.trim())
             } else {
                 input_json.trim().to_string()
             };
@@ -306,6 +309,11 @@ impl OpenGolfCoachApp {
                     // Parse the result to extract all data
                     if let Ok(result) = serde_json::from_str::<serde_json::Value>(&result_json) {
                         if let Some(ogc) = result.get("open_golf_coach") {
+                            let shot_id = ogc
+                                .get("shot_id")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or_default()
+                                .to_string();
                             let shot_name = ogc
                                 .get("shot_name")
                                 .and_then(|v| v.as_str())
@@ -372,6 +380,7 @@ impl OpenGolfCoachApp {
 
                             // Update the latest result
                             *latest_result.lock().unwrap() = ShotResult {
+                                shot_id,
                                 shot_name,
                                 shot_rank,
                                 shot_color_rgb,
@@ -542,9 +551,9 @@ impl OpenGolfCoachApp {
             // Convert OpenAPI format to OpenGolfCoach format
             let ogc_input = Self::convert_openapi_to_ogc(&openapi_data);
 
-            // Apply sign inversion for left-handed golfers before processing
+            // Tell the core library about left-handed golfers before processing
             let processed_input = if is_left_handed.load(Ordering::Relaxed) {
-                invert_signs_for_left_handed(&ogc_input)
+                mark_left_handed(&ogc_input)
             } else {
                 ogc_input
             };
@@ -555,6 +564,11 @@ impl OpenGolfCoachApp {
                     Ok(result_json) => {
                     if let Ok(result) = serde_json::from_str::<serde_json::Value>(&result_json) {
                         if let Some(ogc) = result.get("open_golf_coach") {
+                            let shot_id = ogc
+                                .get("shot_id")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or_default()
+                                .to_string();
                             let shot_name = ogc
                                 .get("shot_name")
                                 .and_then(|v| v.as_str())
@@ -619,6 +633,7 @@ impl OpenGolfCoachApp {
                             let timestamp = now.format("%H:%M:%S").to_string();
 
                             Some(ShotResult {
+                                shot_id,
                                 shot_name,
                                 shot_rank,
                                 shot_color_rgb,
@@ -776,20 +791,13 @@ fn parse_hex_color(hex_str: &str) -> egui::Color32 {
     egui::Color32::WHITE // Fallback
 }
 
-// Invert horizontal launch angle and spin axis for left-handed golfers
-// This transforms the data so the classification system (designed for right-handed)
-// produces the correct shot names for left-handed golfers
-fn invert_signs_for_left_handed(input_json: &str) -> String {
+// Flag the shot as left-handed so the core library reports golfer-relative
+// shot names and club path/face values, with lateral outputs mirrored to match
+fn mark_left_handed(input_json: &str) -> String {
     if let Ok(mut json) = serde_json::from_str::<serde_json::Value>(input_json) {
         if let Some(obj) = json.as_object_mut() {
-            // Invert horizontal_launch_angle_degrees
-            if let Some(h_launch) = obj.get("horizontal_launch_angle_degrees").and_then(|v| v.as_f64()) {
-                obj.insert("horizontal_launch_angle_degrees".to_string(), serde_json::json!(-h_launch));
-            }
-            // Invert spin_axis_degrees
-            if let Some(spin_axis) = obj.get("spin_axis_degrees").and_then(|v| v.as_f64()) {
-                obj.insert("spin_axis_degrees".to_string(), serde_json::json!(-spin_axis));
-            }
+            obj.insert("handedness".to_string(), serde_json::json!("left"));
+            obj.insert("mirror_outputs".to_string(), serde_json::json!(true));
         }
         serde_json::to_string(&json).unwrap_or_else(|_| input_json.to_string())
     } else {
@@ -861,8 +869,7 @@ impl eframe::App for OpenGolfCoachApp {
                 });
 
                 // Add tooltip for shot name
-                let shot_key = result.shot_name.to_lowercase().replace(" ", "_");
-                if let Some(tooltip) = self.shot_tooltips.get(&shot_key) {
+                if let Some(tooltip) = self.shot_tooltips.get(&result.shot_id) {
                     shot_group.response.on_hover_text(tooltip);
                 }
 